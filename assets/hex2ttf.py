@@ -0,0 +1,203 @@
+#!/usr/bin/env python3
+"""Build assets/unifont-cjk.ttf (shipped gzipped) from GNU Unifont's unifont.hex.
+
+Every set pixel of the 16x16 bitmaps becomes part of a rectangle outline,
+one font unit per pixel, keeping only the ranges orient needs a fallback
+for: CJK punctuation, kana, ideographs, Hangul and full-width forms.
+
+    python3 assets/hex2ttf.py unifont.hex assets/unifont-cjk.ttf && gzip -9n assets/unifont-cjk.ttf
+"""
+
+import struct
+import sys
+
+RANGES = [
+    (0x2E80, 0x2FDF),  # CJK radicals
+    (0x3000, 0x30FF),  # CJK punctuation, hiragana, katakana
+    (0x3100, 0x31FF),  # bopomofo, Hangul compatibility jamo, ...
+    (0x3400, 0x4DBF),  # CJK extension A
+    (0x4E00, 0x9FFF),  # CJK unified ideographs
+    (0xAC00, 0xD7A3),  # Hangul syllables
+    (0xF900, 0xFAFF),  # CJK compatibility ideographs
+    (0xFE30, 0xFE4F),  # CJK compatibility forms
+    (0xFF00, 0xFFEF),  # half-width and full-width forms
+]
+ASCENT, DESCENT = 14, 2
+
+
+def wanted(code):
+    return any(lo <= code <= hi for lo, hi in RANGES)
+
+
+def rectangles(rows, width):
+    """Cover the set pixels with rectangles: runs of each row, merged with
+    identical runs of the rows below."""
+    open_runs = {}
+    done = []
+    for y, row in enumerate(rows + [0]):
+        runs = set()
+        x = 0
+        while x < width:
+            if row >> (width - 1 - x) & 1:
+                start = x
+                while x < width and row >> (width - 1 - x) & 1:
+                    x += 1
+                runs.add((start, x))
+            else:
+                x += 1
+        for run, top in list(open_runs.items()):
+            if run not in runs:
+                done.append((run[0], top, run[1], y))
+                del open_runs[run]
+        for run in runs:
+            open_runs.setdefault(run, y)
+    return done
+
+
+def glyph(rows, width):
+    rects = rectangles(rows, width)
+    if not rects:
+        return b""
+    points = []
+    ends = []
+    for x0, y0, x1, y1 in rects:
+        # Rows count down from the top; font units up from the baseline.
+        top, bottom = ASCENT - y0, ASCENT - y1
+        points += [(x0, bottom), (x0, top), (x1, top), (x1, bottom)]
+        ends.append(len(points) - 1)
+    xs = [p[0] for p in points]
+    ys = [p[1] for p in points]
+    out = struct.pack(">hhhhh", len(ends), min(xs), min(ys), max(xs), max(ys))
+    out += struct.pack(">%dH" % len(ends), *ends)
+    out += struct.pack(">H", 0)
+    flags, xdata, ydata = bytearray(), bytearray(), bytearray()
+    last = (0, 0)
+    for x, y in points:
+        flag = 1  # on curve
+        for delta, short, same, data in (
+            (x - last[0], 0x02, 0x10, xdata),
+            (y - last[1], 0x04, 0x20, ydata),
+        ):
+            if delta == 0:
+                flag |= same
+            else:
+                flag |= short
+                if delta > 0:
+                    flag |= same
+                data.append(abs(delta))
+        flags.append(flag)
+        last = (x, y)
+    return out + bytes(flags) + bytes(xdata) + bytes(ydata)
+
+
+def cmap(codes):
+    """A format 12 table mapping `codes`, sorted, to glyphs 1, 2, ..."""
+    groups = []
+    for gid, code in enumerate(codes, 1):
+        if groups and groups[-1][1] + 1 == code and groups[-1][2] + (code - groups[-1][0]) == gid:
+            groups[-1][1] = code
+        else:
+            groups.append([code, code, gid])
+    sub = struct.pack(">HHIII", 12, 0, 16 + 12 * len(groups), 0, len(groups))
+    sub += b"".join(struct.pack(">III", *g) for g in groups)
+    return struct.pack(">HHHHI", 0, 1, 3, 10, 12) + sub
+
+
+def name_table():
+    names = [
+        (0, "Copyright GNU Unifont contributors; SIL Open Font License 1.1"),
+        (1, "Unifont CJK"),
+        (2, "Regular"),
+        (3, "Unifont CJK Regular"),
+        (4, "Unifont CJK"),
+        (5, "Version 15.1.05"),
+        (6, "UnifontCJK-Regular"),
+    ]
+    header = struct.pack(">HHH", 0, len(names), 6 + 12 * len(names))
+    strings = b""
+    for name_id, text in names:
+        data = text.encode("utf-16-be")
+        header += struct.pack(">HHHHHH", 3, 1, 0x409, name_id, len(data), len(strings))
+        strings += data
+    return header + strings
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(">%dI" % (len(data) // 4), data)) & 0xFFFFFFFF
+
+
+def main(hex_path, out_path):
+    glyphs = {}
+    with open(hex_path) as hex_file:
+        for line in hex_file:
+            code, bits = line.strip().split(":")
+            code = int(code, 16)
+            if not wanted(code):
+                continue
+            step = len(bits) // 16  # hex digits per row
+            width = step * 4
+            rows = [int(bits[i : i + step], 16) for i in range(0, len(bits), step)]
+            glyphs[code] = (rows, width)
+    codes = sorted(glyphs)
+
+    glyf = bytearray()
+    loca = [0, 0]  # an empty .notdef
+    advances = [8]
+    max_contours = 0
+    for code in codes:
+        rows, width = glyphs[code]
+        data = glyph(rows, width)
+        if data:
+            max_contours = max(max_contours, struct.unpack(">h", data[:2])[0])
+        glyf += data + b"\0" * (-len(data) % 4)
+        loca.append(len(glyf))
+        advances.append(width)
+    count = len(loca) - 1
+
+    tables = {
+        b"cmap": cmap(codes),
+        b"glyf": bytes(glyf),
+        b"head": struct.pack(
+            ">IIIIHHqqhhhhHHhhh", 0x00010000, 0x00010000, 0, 0x5F0F3CF5, 0x000B, 16,
+            0, 0, 0, -DESCENT, 16, ASCENT, 0, 8, 2, 1, 0,
+        ),
+        b"hhea": struct.pack(
+            ">IhhhHhhhhhhhhhhhH", 0x00010000, ASCENT, -DESCENT, 0, 16, 0, 0, 16,
+            1, 0, 0, 0, 0, 0, 0, 0, count,
+        ),
+        b"hmtx": b"".join(struct.pack(">Hh", a, 0) for a in advances),
+        b"loca": struct.pack(">%dI" % len(loca), *loca),
+        b"maxp": struct.pack(
+            ">IHHHHHHHHHHHHHH", 0x00010000, count, 4 * max_contours, max_contours,
+            0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0,
+        ),
+        b"name": name_table(),
+        b"OS/2": struct.pack(
+            ">HhHHHhhhhhhhhhhh10sIIII4sHHHhhhHH",
+            1, 16, 400, 5, 0, 8, 8, 0, 0, 8, 8, 0, 0, 1, 6, 0,
+            b"\0" * 10, 0, 0, 0, 0, b"GNU ", 0x40, codes[0], min(codes[-1], 0xFFFF),
+            ASCENT, -DESCENT, 0, ASCENT, DESCENT,
+        ) + struct.pack(">II", 0, 0),
+        b"post": struct.pack(">IIhhIIIII", 0x00030000, 0, -2, 1, 1, 0, 0, 0, 0),
+    }
+
+    tags = sorted(tables)
+    offset = 12 + 16 * len(tags)
+    directory = struct.pack(">IHHHH", 0x00010000, len(tags), 128, 3, len(tags) * 16 - 128)
+    body = b""
+    for tag in tags:
+        data = tables[tag]
+        if tag == b"head":
+            head = offset + len(body)
+        directory += struct.pack(">4sIII", tag, checksum(data), offset + len(body), len(data))
+        body += data + b"\0" * (-len(data) % 4)
+    font = bytearray(directory + body)
+    adjustment = (0xB1B0AFBA - checksum(bytes(font))) & 0xFFFFFFFF
+    font[head + 8 : head + 12] = struct.pack(">I", adjustment)
+    with open(out_path, "wb") as out:
+        out.write(font)
+
+
+if __name__ == "__main__":
+    main(*sys.argv[1:3])
@@ -0,0 +1,307 @@
+use serde_json::Value;
+
+use super::Resource;
+use crate::json_path;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Kind {
+    /// The status is `value`.
+    #[default]
+    Status,
+    /// The response has the header `target`, whatever its value.
+    HeaderPresent,
+    /// A value selected by the JSONPath `target` equals `value`.
+    JsonPathEquals,
+    /// A value selected by `target` contains `value`: a substring of a
+    /// string, an element of an array or a key of an object.
+    JsonPathContains,
+    /// The raw body contains the text `value`.
+    BodyContains,
+    /// The response took less than `value` milliseconds.
+    TimeBelow,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 6] = [
+        Kind::Status,
+        Kind::HeaderPresent,
+        Kind::JsonPathEquals,
+        Kind::JsonPathContains,
+        Kind::BodyContains,
+        Kind::TimeBelow,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Kind::Status => "status equals",
+            Kind::HeaderPresent => "header present",
+            Kind::JsonPathEquals => "JSONPath equals",
+            Kind::JsonPathContains => "JSONPath contains",
+            Kind::BodyContains => "body contains",
+            Kind::TimeBelow => "time below (ms)",
+        }
+    }
+
+    /// Whether a header name or path goes in `target`.
+    pub fn has_target(self) -> bool {
+        matches!(
+            self,
+            Kind::HeaderPresent | Kind::JsonPathEquals | Kind::JsonPathContains
+        )
+    }
+
+    /// Whether an expected status or value goes in `value`.
+    pub fn has_value(self) -> bool {
+        !matches!(self, Kind::HeaderPresent)
+    }
+}
+
+/// A check run on every response of a request.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Assertion {
+    pub kind: Kind,
+    /// Header name or JSONPath.
+    pub target: String,
+    /// Expected status or value; JSON if it parses as JSON, else a string.
+    pub value: String,
+}
+
+impl Assertion {
+    pub fn describe(&self) -> String {
+        match self.kind {
+            Kind::Status => format!("status is {}", self.value.trim()),
+            Kind::HeaderPresent => format!("header {} is present", self.target.trim()),
+            Kind::JsonPathEquals => format!("{} equals {}", self.target.trim(), self.value),
+            Kind::JsonPathContains => format!("{} contains {}", self.target.trim(), self.value),
+            Kind::BodyContains => format!("body contains {}", self.value),
+            Kind::TimeBelow => format!("time is below {} ms", self.value.trim()),
+        }
+    }
+}
+
+/// How one assertion fared against a response.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Outcome {
+    pub description: String,
+    /// Why it failed; `None` if it passed.
+    pub failure: Option<String>,
+}
+
+/// `text` as JSON, or as a JSON string if it doesn't parse.
+fn expected(text: &str) -> Value {
+    serde_json::from_str(text.trim()).unwrap_or_else(|_| Value::String(text.to_owned()))
+}
+
+fn contains(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (Value::String(text), Value::String(part)) => text.contains(part.as_str()),
+        (Value::String(text), part) => text.contains(&part.to_string()),
+        (Value::Array(items), expected) => items.contains(expected),
+        (Value::Object(map), Value::String(key)) => map.contains_key(key),
+        _ => false,
+    }
+}
+
+/// Check `assertion` against `resource`.
+pub fn evaluate(assertion: &Assertion, resource: &Resource) -> Result<(), String> {
+    match assertion.kind {
+        Kind::Status => {
+            let expected: usize = assertion
+                .value
+                .trim()
+                .parse()
+                .map_err(|_| format!("`{}` is not a status code", assertion.value.trim()))?;
+            match resource.status {
+                status if status == expected => Ok(()),
+                0 => Err("no response".to_owned()),
+                status => Err(format!("status was {}", status)),
+            }
+        }
+        Kind::HeaderPresent => {
+            let name = assertion.target.trim();
+            match resource
+                .headers
+                .iter()
+                .any(|(key, _)| key.eq_ignore_ascii_case(name))
+            {
+                true => Ok(()),
+                false => Err("no such header".to_owned()),
+            }
+        }
+        Kind::BodyContains => match resource.body.contains(assertion.value.as_str()) {
+            true => Ok(()),
+            false => Err("not in the body".to_owned()),
+        },
+        Kind::TimeBelow => {
+            let limit: u128 = assertion
+                .value
+                .trim()
+                .parse()
+                .map_err(|_| format!("`{}` is not a number of ms", assertion.value.trim()))?;
+            match resource.elapsed_ms {
+                elapsed if elapsed < limit => Ok(()),
+                elapsed => Err(format!("took {} ms", elapsed)),
+            }
+        }
+        Kind::JsonPathEquals | Kind::JsonPathContains => {
+            let body: Value = serde_json::from_str(&resource.body)
+                .map_err(|err| format!("the body isn't JSON: {}", err))?;
+            let values = json_path::query(&body, &assertion.target)?;
+            if values.is_empty() {
+                return Err("the path selects nothing".to_owned());
+            }
+            let expected = expected(&assertion.value);
+            let matches = |value: &&Value| match assertion.kind {
+                Kind::JsonPathEquals => **value == expected,
+                _ => contains(value, &expected),
+            };
+            match values.iter().any(matches) {
+                true => Ok(()),
+                false => Err(format!(
+                    "got {}",
+                    values
+                        .iter()
+                        .map(|value| value.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            }
+        }
+    }
+}
+
+/// Run every assertion with a target or value on `resource`, in order.
+pub fn run(assertions: &[Assertion], resource: &Resource) -> Vec<Outcome> {
+    assertions
+        .iter()
+        .filter(|a| !a.target.trim().is_empty() || !a.value.trim().is_empty())
+        .map(|assertion| Outcome {
+            description: assertion.describe(),
+            failure: evaluate(assertion, resource).err(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Resource {
+        Resource {
+            status: 201,
+            headers: vec![("content-type".to_owned(), "application/json".to_owned())],
+            body: r#"{"data": {"id": 7, "name": "widget", "tags": ["a", "b"]}}"#.to_owned(),
+            elapsed_ms: 120,
+            ..Resource::default()
+        }
+    }
+
+    fn check(kind: Kind, target: &str, value: &str) -> Result<(), String> {
+        let assertion = Assertion {
+            kind,
+            target: target.to_owned(),
+            value: value.to_owned(),
+        };
+        evaluate(&assertion, &sample())
+    }
+
+    #[test]
+    fn status() {
+        assert_eq!(check(Kind::Status, "", " 201 "), Ok(()));
+        assert_eq!(
+            check(Kind::Status, "", "200"),
+            Err("status was 201".to_owned())
+        );
+        assert_eq!(
+            check(Kind::Status, "", "ok"),
+            Err("`ok` is not a status code".to_owned())
+        );
+        let unsent = Resource::default();
+        let assertion = Assertion {
+            value: "200".to_owned(),
+            ..Assertion::default()
+        };
+        assert_eq!(evaluate(&assertion, &unsent), Err("no response".to_owned()));
+    }
+
+    #[test]
+    fn header_present() {
+        assert_eq!(check(Kind::HeaderPresent, "Content-Type", ""), Ok(()));
+        assert_eq!(
+            check(Kind::HeaderPresent, "etag", ""),
+            Err("no such header".to_owned())
+        );
+    }
+
+    #[test]
+    fn body_contains() {
+        assert_eq!(
+            check(Kind::BodyContains, "", "\"name\": \"widget\""),
+            Ok(())
+        );
+        assert_eq!(
+            check(Kind::BodyContains, "", "gadget"),
+            Err("not in the body".to_owned())
+        );
+    }
+
+    #[test]
+    fn json_path_equals() {
+        assert_eq!(check(Kind::JsonPathEquals, "$.data.id", "7"), Ok(()));
+        assert_eq!(check(Kind::JsonPathEquals, "$.data.name", "widget"), Ok(()));
+        assert_eq!(
+            check(Kind::JsonPathEquals, "$.data.id", "8"),
+            Err("got 7".to_owned())
+        );
+        assert_eq!(
+            check(Kind::JsonPathEquals, "$.data.missing", "1"),
+            Err("the path selects nothing".to_owned())
+        );
+    }
+
+    #[test]
+    fn json_path_contains() {
+        assert_eq!(check(Kind::JsonPathContains, "$.data.name", "idg"), Ok(()));
+        assert_eq!(check(Kind::JsonPathContains, "$.data.tags", "b"), Ok(()));
+        assert_eq!(check(Kind::JsonPathContains, "$.data", "id"), Ok(()));
+        assert_eq!(
+            check(Kind::JsonPathContains, "$.data.tags", "c"),
+            Err(r#"got ["a","b"]"#.to_owned())
+        );
+    }
+
+    #[test]
+    fn time_below() {
+        assert_eq!(check(Kind::TimeBelow, "", "500"), Ok(()));
+        assert_eq!(
+            check(Kind::TimeBelow, "", "100"),
+            Err("took 120 ms".to_owned())
+        );
+        assert_eq!(
+            check(Kind::TimeBelow, "", "fast"),
+            Err("`fast` is not a number of ms".to_owned())
+        );
+    }
+
+    #[test]
+    fn run_skips_empty_rows() {
+        let outcomes = run(
+            &[
+                Assertion::default(),
+                Assertion {
+                    value: "404".to_owned(),
+                    ..Assertion::default()
+                },
+            ],
+            &sample(),
+        );
+        assert_eq!(
+            outcomes,
+            [Outcome {
+                description: "status is 404".to_owned(),
+                failure: Some("status was 201".to_owned()),
+            }]
+        );
+    }
+}
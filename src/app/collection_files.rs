@@ -0,0 +1,154 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use super::{Directory, HttpApp, Location};
+
+/// Ending of the files `save` writes, one per folder; other files in the
+/// directory are left alone.
+const SUFFIX: &str = ".folder.json";
+
+/// A folder and its requests, as stored in its own file.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct FolderFile {
+    directory: Directory,
+    requests: Vec<Location>,
+}
+
+/// `<name>-<id prefix>.folder.json`, readable and still unique per folder.
+fn file_name(directory: &Directory) -> String {
+    let name: String = directory
+        .name
+        .chars()
+        .map(|c| match c {
+            c if c.is_alphanumeric() || c == '-' || c == '_' => c,
+            _ => '_',
+        })
+        .collect();
+    let id: String = directory.id.chars().take(8).collect();
+    format!("{}-{}{}", name, id, SUFFIX)
+}
+
+/// Write every folder with its requests to a file of its own in `dir`, and
+/// remove the files of folders that no longer exist. Returns how many
+/// folders were written.
+pub fn save(app: &HttpApp, dir: &Path) -> std::io::Result<usize> {
+    fs::create_dir_all(dir)?;
+    let mut written = BTreeSet::new();
+    for directory in app.directory.values() {
+        let folder = FolderFile {
+            directory: directory.clone(),
+            requests: directory
+                .locations
+                .iter()
+                .filter_map(|id| app.context.api_collection.buffers.get(id))
+                .cloned()
+                .collect(),
+        };
+        let name = file_name(directory);
+        let mut json = serde_json::to_string_pretty(&folder)?;
+        json.push('\n');
+        fs::write(dir.join(&name), json)?;
+        written.insert(name);
+    }
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        if name.ends_with(SUFFIX) && !written.contains(&name) {
+            fs::remove_file(dir.join(&name))?;
+        }
+    }
+    Ok(written.len())
+}
+
+/// Read the folder files in `dir` into the workspace. Folders and requests
+/// with the same id as existing ones replace them. Returns a summary.
+pub fn open(app: &mut HttpApp, dir: &Path) -> std::io::Result<String> {
+    let mut folders = 0;
+    let mut requests = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.to_string_lossy().ends_with(SUFFIX) {
+            continue;
+        }
+        let folder: FolderFile = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        folders += 1;
+        requests += folder.requests.len();
+        for location in folder.requests {
+            app.context
+                .api_collection
+                .buffers
+                .insert(location.id.clone(), location);
+        }
+        app.directory
+            .insert(folder.directory.id.clone(), folder.directory);
+    }
+    Ok(format!(
+        "Opened {} folders with {} requests from {}",
+        folders,
+        requests,
+        dir.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_with(folders: &[(&str, &str, &[&str])]) -> HttpApp {
+        let mut app = HttpApp::default();
+        app.directory.clear();
+        app.context.api_collection.buffers.clear();
+        for (id, name, requests) in folders {
+            for request in *requests {
+                let location = Location {
+                    id: request.to_string(),
+                    name: request.to_string(),
+                    url: format!("https://example.com/{}", request),
+                    ..Location::default()
+                };
+                app.context
+                    .api_collection
+                    .buffers
+                    .insert(request.to_string(), location);
+            }
+            let directory = Directory {
+                id: id.to_string(),
+                name: name.to_string(),
+                locations: requests.iter().map(|r| r.to_string()).collect(),
+                ..Directory::default()
+            };
+            app.directory.insert(id.to_string(), directory);
+        }
+        app
+    }
+
+    #[test]
+    fn folders_read_back_as_saved() {
+        let dir = std::env::temp_dir().join(format!("orient-{}", uuid::Uuid::new_v4()));
+        let saved = app_with(&[
+            ("a1b2c3d4e5", "Users / admin", &["list", "create"]),
+            ("f6", "Orders", &["get"]),
+        ]);
+        assert_eq!(save(&saved, &dir).unwrap(), 2);
+        assert!(dir.join("Users___admin-a1b2c3d4.folder.json").exists());
+
+        let mut opened = app_with(&[]);
+        let summary = open(&mut opened, &dir).unwrap();
+        assert!(
+            summary.starts_with("Opened 2 folders with 3 requests"),
+            "{}",
+            summary
+        );
+        assert_eq!(opened.directory, saved.directory);
+        assert_eq!(
+            opened.context.api_collection.buffers,
+            saved.context.api_collection.buffers
+        );
+
+        // Files of deleted folders go on the next save.
+        assert_eq!(save(&app_with(&[("f6", "Orders", &[])]), &dir).unwrap(), 1);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
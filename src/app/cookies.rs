@@ -0,0 +1,279 @@
+/// A cookie set by a response's `Set-Cookie` header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cookie {
+    pub name: String,
+    /// The value as sent, still percent-encoded if the server encoded it.
+    pub value: String,
+    /// `Path=/`, `HttpOnly` and the like, as written by the server.
+    pub attributes: Vec<String>,
+}
+
+impl Cookie {
+    /// `name=value`, as it goes into a `Cookie` request header.
+    pub fn pair(&self) -> String {
+        format!("{}={}", self.name, self.value)
+    }
+
+    /// Value of the attribute `name`, case-insensitively; flags like
+    /// `Secure` have an empty value.
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find_map(|attribute| {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then_some(value.trim())
+        })
+    }
+
+    /// Whether the server asked for the cookie to be deleted: a `Max-Age`
+    /// of zero or less, or an `Expires` date in an earlier year.
+    fn expired(&self) -> bool {
+        if let Some(max_age) = self.attribute("Max-Age") {
+            return max_age.parse::<i64>().is_ok_and(|age| age <= 0);
+        }
+        let Some(expires) = self.attribute("Expires") else {
+            return false;
+        };
+        // Only the year is looked at; deletions use dates like 1970.
+        let this_year = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(1970, |d| 1970 + d.as_secs() / 31_556_952);
+        expires
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|part| part.len() == 4)
+            .filter_map(|part| part.parse::<u64>().ok())
+            .any(|year| year < this_year)
+    }
+}
+
+pub fn parse_set_cookie(header: &str) -> Option<Cookie> {
+    let mut parts = header.split(';').map(str::trim);
+    let (name, value) = parts.next()?.split_once('=')?;
+    if name.trim().is_empty() {
+        return None;
+    }
+    Some(Cookie {
+        name: name.trim().to_owned(),
+        value: value.trim().trim_matches('"').to_owned(),
+        attributes: parts.filter(|p| !p.is_empty()).map(str::to_owned).collect(),
+    })
+}
+
+/// Cookies set by a response, in header order.
+pub fn from_headers(headers: &[(String, String)]) -> Vec<Cookie> {
+    headers
+        .iter()
+        .filter(|(k, _)| k.eq_ignore_ascii_case("Set-Cookie"))
+        .filter_map(|(_, v)| parse_set_cookie(v))
+        .collect()
+}
+
+/// `Cookie` header value replaying `cookies`.
+pub fn header_value<'a>(cookies: impl IntoIterator<Item = &'a Cookie>) -> String {
+    cookies
+        .into_iter()
+        .map(Cookie::pair)
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+/// Decode `%XX` escapes for display; invalid escapes are kept as they are.
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                out.push(byte);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// A cookie kept by the jar, with where it may be sent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredCookie {
+    pub cookie: Cookie,
+    pub domain: String,
+    /// Without a `Domain` attribute a cookie only goes back to the exact host.
+    pub host_only: bool,
+    pub path: String,
+    pub secure: bool,
+}
+
+impl StoredCookie {
+    fn matches(&self, url: &url::Url) -> bool {
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        let domain_matches = host == self.domain
+            || (!self.host_only && host.ends_with(&format!(".{}", self.domain)));
+        let path = url.path();
+        let path_matches = path == self.path
+            || (path.starts_with(&self.path)
+                && (self.path.ends_with('/') || path[self.path.len()..].starts_with('/')));
+        domain_matches && path_matches && (!self.secure || url.scheme() == "https")
+    }
+}
+
+/// Cookies set by responses, sent back with later requests to the same host
+/// like a browser would. Kept for the session of the open workspace.
+#[derive(Default)]
+pub struct Jar {
+    cookies: Vec<StoredCookie>,
+}
+
+impl Jar {
+    pub fn cookies(&self) -> &[StoredCookie] {
+        &self.cookies
+    }
+
+    pub fn clear(&mut self) {
+        self.cookies.clear();
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.cookies.len() {
+            self.cookies.remove(index);
+        }
+    }
+
+    /// Keep the cookies a response from `url` set, replacing older ones with
+    /// the same name, domain and path.
+    pub fn store(&mut self, url: &str, headers: &[(String, String)]) {
+        let Ok(url) = url::Url::parse(url) else {
+            return;
+        };
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        for cookie in from_headers(headers) {
+            let (domain, host_only) = match cookie.attribute("Domain") {
+                Some(domain) if !domain.is_empty() => {
+                    (domain.trim_start_matches('.').to_ascii_lowercase(), false)
+                }
+                _ => (host.clone(), true),
+            };
+            // A server can only set cookies for itself and its parent domains.
+            if host != domain && !host.ends_with(&format!(".{}", domain)) {
+                log::warn!(
+                    "ignoring cookie {} for {} set by {}",
+                    cookie.name,
+                    domain,
+                    host
+                );
+                continue;
+            }
+            let path = match cookie.attribute("Path") {
+                Some(path) if path.starts_with('/') => path.to_owned(),
+                // The default path is the directory of the request path.
+                _ => match url.path().rfind('/') {
+                    Some(0) | None => "/".to_owned(),
+                    Some(end) => url.path()[..end].to_owned(),
+                },
+            };
+            self.cookies.retain(|stored| {
+                !(stored.cookie.name == cookie.name
+                    && stored.domain == domain
+                    && stored.path == path)
+            });
+            if cookie.expired() {
+                continue;
+            }
+            self.cookies.push(StoredCookie {
+                secure: cookie.attribute("Secure").is_some(),
+                cookie,
+                domain,
+                host_only,
+                path,
+            });
+        }
+    }
+
+    /// `Cookie` header value for a request to `url`, if any cookie applies.
+    /// Longer paths go first, as browsers do.
+    pub fn header_for(&self, url: &str) -> Option<String> {
+        let url = url::Url::parse(url).ok()?;
+        let mut cookies: Vec<&StoredCookie> =
+            self.cookies.iter().filter(|c| c.matches(&url)).collect();
+        if cookies.is_empty() {
+            return None;
+        }
+        cookies.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+        Some(header_value(cookies.into_iter().map(|c| &c.cookie)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_cookie(values: &[&str]) -> Vec<(String, String)> {
+        values
+            .iter()
+            .map(|v| ("Set-Cookie".to_owned(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_set_cookie() {
+        assert_eq!(
+            parse_set_cookie("id=\"a%20b\"; Path=/; HttpOnly"),
+            Some(Cookie {
+                name: "id".to_owned(),
+                value: "a%20b".to_owned(),
+                attributes: vec!["Path=/".to_owned(), "HttpOnly".to_owned()],
+            })
+        );
+        assert_eq!(parse_set_cookie("no-value"), None);
+        assert_eq!(parse_set_cookie("=x"), None);
+        assert_eq!(percent_decode("a%20b%zz"), "a b%zz");
+    }
+
+    #[test]
+    fn sends_stored_cookies_back() {
+        let mut jar = Jar::default();
+        jar.store(
+            "http://shop.example/account/login",
+            &set_cookie(&["session=abc", "theme=dark; Path=/; Domain=example"]),
+        );
+        assert_eq!(
+            jar.header_for("http://shop.example/account/orders")
+                .as_deref(),
+            Some("session=abc; theme=dark")
+        );
+        // Host-only cookies don't go to subdomains, nor outside their path.
+        assert_eq!(
+            jar.header_for("http://api.shop.example/").as_deref(),
+            Some("theme=dark")
+        );
+        assert_eq!(
+            jar.header_for("http://shop.example/cart").as_deref(),
+            Some("theme=dark")
+        );
+        assert_eq!(jar.header_for("http://other.test/"), None);
+    }
+
+    #[test]
+    fn replaces_and_deletes_cookies() {
+        let mut jar = Jar::default();
+        let url = "https://shop.example/";
+        jar.store(url, &set_cookie(&["a=1", "b=2; Secure", "c=3"]));
+        jar.store(url, &set_cookie(&["a=10", "c=; Max-Age=0"]));
+        assert_eq!(jar.header_for(url).as_deref(), Some("b=2; a=10"));
+        assert_eq!(
+            jar.header_for("http://shop.example/").as_deref(),
+            Some("a=10")
+        );
+        // A server can't set cookies for other domains.
+        jar.store(url, &set_cookie(&["x=1; Domain=bank.example"]));
+        assert_eq!(jar.cookies().len(), 2);
+    }
+}
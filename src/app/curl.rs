@@ -0,0 +1,638 @@
+use uuid::Uuid;
+
+use super::{graphql, query, ApiKeyPlacement, Auth, ContentType, Location, Method};
+use crate::hex;
+
+/// Split a shell command line into words, handling single, double and `$'…'`
+/// quotes, backslash escapes and `\` line continuations.
+fn split_words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // Browsers quote bodies with control characters as `$'…'`.
+            // `\xNN` escapes are bytes, so a multi-byte UTF-8 character
+            // spans several of them; decode once the quote is closed.
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                let mut bytes = Vec::new();
+                let push = |bytes: &mut Vec<u8>, c: char| {
+                    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes())
+                };
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\'' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => bytes.push(b'\n'),
+                            Some('r') => bytes.push(b'\r'),
+                            Some('t') => bytes.push(b'\t'),
+                            Some(escape @ ('x' | 'u' | 'U')) => {
+                                let digits = match escape {
+                                    'x' => 2,
+                                    'u' => 4,
+                                    _ => 8,
+                                };
+                                let mut hex = String::new();
+                                while hex.len() < digits
+                                    && chars.peek().is_some_and(char::is_ascii_hexdigit)
+                                {
+                                    hex.extend(chars.next());
+                                }
+                                let code = u32::from_str_radix(&hex, 16).ok();
+                                match (escape, code) {
+                                    ('x', Some(byte)) => bytes.push(byte as u8),
+                                    (_, Some(code)) => {
+                                        push(&mut bytes, char::from_u32(code).unwrap_or('\u{fffd}'))
+                                    }
+                                    (_, None) => {
+                                        bytes.push(b'\\');
+                                        push(&mut bytes, escape);
+                                    }
+                                }
+                            }
+                            Some(c) => push(&mut bytes, c),
+                            None => {}
+                        },
+                        c => push(&mut bytes, c),
+                    }
+                }
+                word.push_str(&String::from_utf8_lossy(&bytes));
+            }
+            '\'' => {
+                in_word = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    word.push(c);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if matches!(chars.peek(), Some('"' | '\\' | '$' | '`')) => {
+                            word.push(chars.next().unwrap());
+                        }
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') | Some('\r') | None => {}
+                Some(c) => {
+                    in_word = true;
+                    word.push(c);
+                }
+            },
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+/// Options that take an argument, which can also be attached: `-XPOST`,
+/// `-H'Accept: */*'` or `--request=POST`.
+const WITH_ARGUMENT: &[&str] = &[
+    "-X",
+    "--request",
+    "-H",
+    "--header",
+    "-d",
+    "--data",
+    "--data-raw",
+    "--data-binary",
+    "--data-ascii",
+    "--data-urlencode",
+    "-F",
+    "--form",
+    "--json",
+    "--url",
+    "-A",
+    "--user-agent",
+    "-e",
+    "--referer",
+    "-b",
+    "--cookie",
+    "-u",
+    "--user",
+    "--oauth2-bearer",
+    "-o",
+    "--output",
+    "-x",
+    "--proxy",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "-w",
+    "--write-out",
+];
+
+/// Split attached option arguments into words of their own.
+fn detach_arguments(words: Vec<String>) -> Vec<String> {
+    let mut out = Vec::with_capacity(words.len());
+    for word in words {
+        let split = if word.starts_with("--") {
+            word.split_once('=')
+                .filter(|(option, _)| WITH_ARGUMENT.contains(option))
+                .map(|(option, value)| (option.to_owned(), value.to_owned()))
+        } else if word.starts_with('-') && word.len() > 2 && word.is_char_boundary(2) {
+            let (option, value) = word.split_at(2);
+            WITH_ARGUMENT
+                .contains(&option)
+                .then(|| (option.to_owned(), value.to_owned()))
+        } else {
+            None
+        };
+        match split {
+            Some((option, value)) => {
+                out.push(option);
+                out.push(value);
+            }
+            None => out.push(word),
+        }
+    }
+    out
+}
+
+fn key_value(pair: &str, separator: char) -> (String, String) {
+    match pair.split_once(separator) {
+        Some((k, v)) => (k.trim().to_owned(), v.trim().to_owned()),
+        None => (pair.trim().to_owned(), "".to_owned()),
+    }
+}
+
+/// Parse a `curl` command line into a request. Options orient has no
+/// equivalent for (`-s`, `-L`, `-k`, ...) are skipped.
+pub fn parse(command: &str) -> Option<Location> {
+    let words = detach_arguments(split_words(command));
+    let mut words = words.iter();
+    if words.next().map(String::as_str) != Some("curl") {
+        return None;
+    }
+
+    let mut method = None;
+    let mut url = None;
+    let mut header = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut form = Vec::new();
+    let mut get = false;
+    let mut head = false;
+    let mut auth = Auth::None;
+    let mut proxy = String::new();
+    while let Some(word) = words.next() {
+        // Attached arguments such as `--header=value` and `-HValue` were
+        // split off by `detach_arguments`.
+        match word.as_str() {
+            "-X" | "--request" => method = words.next().cloned(),
+            "-H" | "--header" => {
+                if let Some(value) = words.next() {
+                    header.push(key_value(value, ':'));
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii"
+            | "--data-urlencode" => data.extend(words.next().cloned()),
+            "-F" | "--form" => {
+                if let Some(value) = words.next() {
+                    form.push(key_value(value, '='));
+                }
+            }
+            "--json" => {
+                data.extend(words.next().cloned());
+                header.push(("Content-Type".to_owned(), "application/json".to_owned()));
+                header.push(("Accept".to_owned(), "application/json".to_owned()));
+            }
+            "-G" | "--get" => get = true,
+            "-I" | "--head" => head = true,
+            // We decode gzip and deflate bodies, so ask for them as curl would.
+            "--compressed" => {
+                header.push(("Accept-Encoding".to_owned(), "gzip, deflate".to_owned()));
+            }
+            "--url" => url = words.next().cloned(),
+            "-A" | "--user-agent" => {
+                if let Some(value) = words.next() {
+                    header.push(("User-Agent".to_owned(), value.clone()));
+                }
+            }
+            "-e" | "--referer" => {
+                if let Some(value) = words.next() {
+                    header.push(("Referer".to_owned(), value.clone()));
+                }
+            }
+            "-b" | "--cookie" => {
+                if let Some(value) = words.next() {
+                    header.push(("Cookie".to_owned(), value.clone()));
+                }
+            }
+            "-u" | "--user" => {
+                if let Some(value) = words.next() {
+                    let (username, password) = value.split_once(':').unwrap_or((value, ""));
+                    auth = Auth::Basic {
+                        username: username.to_owned(),
+                        password: password.to_owned(),
+                    };
+                }
+            }
+            "--oauth2-bearer" => {
+                if let Some(token) = words.next() {
+                    auth = Auth::Bearer {
+                        token: token.clone(),
+                    };
+                }
+            }
+            "-x" | "--proxy" => proxy = words.next().cloned().unwrap_or_default(),
+            "--socks5" | "--socks5-hostname" => {
+                if let Some(value) = words.next() {
+                    proxy = format!("socks5://{}", value);
+                }
+            }
+            "--socks4" | "--socks4a" => {
+                if let Some(value) = words.next() {
+                    proxy = format!("{}://{}", &word[2..], value);
+                }
+            }
+            // Options taking an argument that is ignored.
+            "-o" | "--output" | "-m" | "--max-time" | "--connect-timeout" | "-w"
+            | "--write-out" => {
+                words.next();
+            }
+            w if w.starts_with('-') => {}
+            w => {
+                if url.is_none() {
+                    url = Some(w.to_owned());
+                }
+            }
+        }
+    }
+    let url = url?;
+
+    let form_encoded = header.iter().any(|(k, v)| {
+        k.eq_ignore_ascii_case("Content-Type") && v.contains("application/x-www-form-urlencoded")
+    });
+    let body = data.join("&");
+    let mut location = Location {
+        id: Uuid::new_v4().to_string(),
+        name: url.clone(),
+        url,
+        header,
+        auth,
+        proxy,
+        ..Default::default()
+    };
+    query::absorb(&mut location.url, &mut location.params);
+    if get {
+        location.params.extend(
+            body.split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| key_value(pair, '=')),
+        );
+    } else if !form.is_empty() {
+        location.content_type = ContentType::FormData;
+        location.form_params = form;
+    } else if form_encoded || (!data.is_empty() && !looks_like_json(&body)) {
+        location.content_type = ContentType::FormUrlEncoded;
+        location.form_params = body
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| key_value(pair, '='))
+            .collect();
+    } else {
+        location.body = body;
+    }
+    let sends_data = !get && (!data.is_empty() || !location.form_params.is_empty());
+    location.method = match method {
+        Some(method) => Method::from_text(method),
+        None if head => Method::Head,
+        None if sends_data => Method::Post,
+        None => Method::Get,
+    };
+    Some(location)
+}
+
+fn looks_like_json(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text).is_ok()
+}
+
+/// Quote `text` as one word for a POSIX shell.
+fn shell_quote(text: &str) -> String {
+    let plain = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if plain {
+        return text.to_owned();
+    }
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// A `curl` command sending `location`, one option per line. Fails if the
+/// body can't be built.
+pub fn to_command(location: &Location) -> Result<String, String> {
+    let mut args = vec!["curl".to_owned()];
+    // Command whose output is piped in as the body.
+    let mut pipe = None;
+    if location.method != Method::Get {
+        args.push(format!("-X {}", location.method.to_text()));
+    }
+    let mut params = location.params.clone();
+    match &location.auth {
+        Auth::ApiKey {
+            key,
+            value,
+            placement: ApiKeyPlacement::Query,
+        } if !key.is_empty() => params.push((key.clone(), value.clone())),
+        _ => {}
+    }
+    args.push(shell_quote(&query::join(&location.url, &params)));
+    if !location.proxy.trim().is_empty() {
+        args.push(format!("-x {}", shell_quote(location.proxy.trim())));
+    }
+
+    let mut header: Vec<(String, String)> = location
+        .header
+        .iter()
+        .filter(|(key, _)| !key.is_empty())
+        .cloned()
+        .collect();
+    match &location.auth {
+        Auth::ApiKey {
+            key,
+            value,
+            placement: ApiKeyPlacement::Header,
+        } if !key.is_empty() => header.push((key.clone(), value.clone())),
+        Auth::Basic { username, password } => {
+            args.push(format!(
+                "-u {}",
+                shell_quote(&format!("{}:{}", username, password))
+            ));
+        }
+        auth => {
+            if let Some(authorization) = auth.authorization() {
+                header.push(("Authorization".to_owned(), authorization));
+            }
+        }
+    }
+    let has_content_type = header
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case("Content-Type"));
+
+    let mut body = Vec::new();
+    if location.method.sends_body() {
+        match location.content_type {
+            ContentType::Json => {
+                if !has_content_type {
+                    header.push(("Content-Type".to_owned(), "application/json".to_owned()));
+                }
+                body.push(format!("-d {}", shell_quote(&location.body)));
+            }
+            ContentType::GraphQl => {
+                if !has_content_type {
+                    header.push(("Content-Type".to_owned(), "application/json".to_owned()));
+                }
+                let json = graphql::body(
+                    &location.body,
+                    &location.graphql_variables,
+                    &location.graphql_operation,
+                )
+                .map_err(|err| format!("can't build the GraphQL body: {}", err))?;
+                body.push(format!("-d {}", shell_quote(&json)));
+            }
+            ContentType::FormUrlEncoded => {
+                for (key, value) in location.form_params.iter().filter(|(k, _)| !k.is_empty()) {
+                    body.push(format!(
+                        "--data-urlencode {}",
+                        shell_quote(&format!("{}={}", key, value))
+                    ));
+                }
+            }
+            ContentType::FormData => {
+                for (key, value) in location.form_params.iter().filter(|(k, _)| !k.is_empty()) {
+                    body.push(format!("-F {}", shell_quote(&format!("{}={}", key, value))));
+                }
+            }
+            ContentType::RawHex => {
+                if !has_content_type {
+                    let content_type = match location.raw_content_type.trim() {
+                        "" => "application/octet-stream",
+                        content_type => content_type,
+                    };
+                    header.push(("Content-Type".to_owned(), content_type.to_owned()));
+                }
+                let bytes = hex::decode(&location.body)
+                    .map_err(|err| format!("invalid hex body: {}", err))?;
+                // `$'\xNN'` is bash-only, and bash cuts it at a NUL byte;
+                // POSIX printf writes any byte given as an octal escape.
+                let escaped: String = bytes.iter().map(|b| format!("\\0{:03o}", b)).collect();
+                pipe = Some(format!("printf '%b' '{}'", escaped));
+                body.push("--data-binary @-".to_owned());
+            }
+            ContentType::Raw => {
+                if !has_content_type {
+                    let content_type = match location.raw_content_type.trim() {
+                        "" => "text/plain",
+                        content_type => content_type,
+                    };
+                    header.push(("Content-Type".to_owned(), content_type.to_owned()));
+                }
+                body.push(format!("--data-binary {}", shell_quote(&location.body)));
+            }
+        }
+    }
+    for (key, value) in header {
+        args.push(format!(
+            "-H {}",
+            shell_quote(&format!("{}: {}", key, value))
+        ));
+    }
+    args.extend(body);
+    let command = args.join(" \\\n  ");
+    Ok(match pipe {
+        Some(pipe) => format!("{} | {}", pipe, command),
+        None => command,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_proxies() {
+        let location = parse("curl -x http://proxy:3128 https://example.com").unwrap();
+        assert_eq!(location.proxy, "http://proxy:3128");
+        let location = parse("curl --socks5-hostname localhost:1080 https://example.com").unwrap();
+        assert_eq!(location.proxy, "socks5://localhost:1080");
+        let location = parse("curl --socks4 localhost:1080 https://example.com").unwrap();
+        assert_eq!(location.proxy, "socks4://localhost:1080");
+        assert_eq!(location.url, "https://example.com");
+    }
+
+    #[test]
+    fn splits_quoted_words() {
+        assert_eq!(
+            split_words("curl -H 'A: b' \"c \\\"d\\\"\" \\\n e\\ f"),
+            vec!["curl", "-H", "A: b", "c \"d\"", "e f"]
+        );
+    }
+
+    #[test]
+    fn decodes_ansi_c_quotes_as_utf8() {
+        assert_eq!(
+            split_words(r"$'caf\xc3\xa9\n\u00e9\U0001F600\t\'x'"),
+            vec!["café\né😀\t'x"]
+        );
+        assert_eq!(split_words(r"$'\xzz'"), vec!["\\xzz"]);
+    }
+
+    #[test]
+    fn detaches_attached_arguments() {
+        let location =
+            parse("curl -XPUT --header=Accept:text/plain -HX-Id:1 https://a.test").unwrap();
+        assert_eq!(location.method, Method::Put);
+        assert_eq!(
+            location.header,
+            vec![
+                ("Accept".to_owned(), "text/plain".to_owned()),
+                ("X-Id".to_owned(), "1".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn pipes_binary_bodies_through_printf() {
+        let location = Location {
+            url: "https://example.com".to_owned(),
+            method: Method::Post,
+            content_type: ContentType::RawHex,
+            body: "01 00 ff".to_owned(),
+            ..Location::default()
+        };
+        let command = to_command(&location).unwrap();
+        assert!(
+            command.starts_with(r"printf '%b' '\0001\0000\0377' | curl"),
+            "{}",
+            command
+        );
+        assert!(command.ends_with("--data-binary @-"), "{}", command);
+        let invalid = Location {
+            body: "0g".to_owned(),
+            ..location
+        };
+        assert!(to_command(&invalid).is_err());
+    }
+
+    #[test]
+    fn parses_copied_browser_commands() {
+        let location = parse(
+            "curl 'https://api.example.com/v1/items?page=2&sort=name' \\\n  -H 'accept: application/json' \\\n  -H 'cookie: session=abc' \\\n  --compressed",
+        )
+        .unwrap();
+        assert_eq!(location.method, Method::Get);
+        assert_eq!(location.url, "https://api.example.com/v1/items");
+        assert_eq!(
+            location.params,
+            vec![
+                ("page".to_owned(), "2".to_owned()),
+                ("sort".to_owned(), "name".to_owned())
+            ]
+        );
+        assert_eq!(
+            location.header,
+            vec![
+                ("accept".to_owned(), "application/json".to_owned()),
+                ("cookie".to_owned(), "session=abc".to_owned()),
+                ("Accept-Encoding".to_owned(), "gzip, deflate".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_json_and_form_posts() {
+        let location = parse(
+            "curl -X POST https://api.example.com/users \\\n  -H \"Content-Type: application/json\" \\\n  -u admin:secret \\\n  -d '{\"name\": \"Ann\"}'",
+        )
+        .unwrap();
+        assert_eq!(location.method, Method::Post);
+        assert_eq!(location.content_type, ContentType::Json);
+        assert_eq!(location.body, r#"{"name": "Ann"}"#);
+        assert_eq!(
+            location.auth,
+            Auth::Basic {
+                username: "admin".to_owned(),
+                password: "secret".to_owned()
+            }
+        );
+
+        let location = parse("curl https://example.com/login -d user=ann -d 'pass=a b'").unwrap();
+        assert_eq!(location.method, Method::Post);
+        assert_eq!(location.content_type, ContentType::FormUrlEncoded);
+        assert_eq!(
+            location.form_params,
+            vec![
+                ("user".to_owned(), "ann".to_owned()),
+                ("pass".to_owned(), "a b".to_owned())
+            ]
+        );
+
+        assert!(parse("wget https://example.com").is_none());
+        assert!(parse("curl -v").is_none());
+    }
+
+    #[test]
+    fn writes_get_json_and_form_commands() {
+        let get = Location {
+            url: "https://example.com/items".to_owned(),
+            params: vec![
+                ("q".to_owned(), "a b".to_owned()),
+                ("page".to_owned(), "2".to_owned()),
+            ],
+            ..Location::default()
+        };
+        assert_eq!(
+            to_command(&get).unwrap(),
+            "curl \\\n  'https://example.com/items?q=a+b&page=2'"
+        );
+
+        let json = Location {
+            url: "https://example.com/items".to_owned(),
+            method: Method::Post,
+            body: r#"{"name": "it's"}"#.to_owned(),
+            ..Location::default()
+        };
+        assert_eq!(
+            to_command(&json).unwrap(),
+            "curl \\\n  -X POST \\\n  https://example.com/items \\\n  -H 'Content-Type: application/json' \\\n  -d '{\"name\": \"it'\\''s\"}'"
+        );
+
+        let form = Location {
+            url: "https://example.com/upload".to_owned(),
+            method: Method::Post,
+            content_type: ContentType::FormData,
+            form_params: vec![
+                ("title".to_owned(), "cat".to_owned()),
+                ("file".to_owned(), "@cat.png".to_owned()),
+            ],
+            ..Location::default()
+        };
+        assert_eq!(
+            to_command(&form).unwrap(),
+            "curl \\\n  -X POST \\\n  https://example.com/upload \\\n  -F title=cat \\\n  -F file=@cat.png"
+        );
+    }
+}
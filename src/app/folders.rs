@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::Directory;
+
+/// Whether following the parents of `id` leads back to it.
+fn in_cycle(directory: &BTreeMap<String, Directory>, id: &str) -> bool {
+    let mut seen = BTreeSet::new();
+    let mut current = id;
+    while let Some(dir) = directory.get(current) {
+        if !seen.insert(current) {
+            // Only `id` itself repeating means it's in the cycle; otherwise
+            // its chain merely runs into one.
+            return current == id;
+        }
+        if dir.parent.is_empty() {
+            return false;
+        }
+        current = &dir.parent;
+    }
+    false
+}
+
+/// The parent `id` is shown under: `""` for top-level folders, and for
+/// folders whose parent is gone or part of a cycle.
+fn shown_parent<'a>(directory: &'a BTreeMap<String, Directory>, id: &str) -> &'a str {
+    match directory.get(id) {
+        Some(dir)
+            if !dir.parent.is_empty()
+                && directory.contains_key(&dir.parent)
+                && !in_cycle(directory, id) =>
+        {
+            &dir.parent
+        }
+        _ => "",
+    }
+}
+
+/// The top-level folder `id` is shown under, `id` itself if it is one.
+pub fn root<'a>(directory: &'a BTreeMap<String, Directory>, id: &'a str) -> &'a str {
+    let mut current = id;
+    loop {
+        match shown_parent(directory, current) {
+            "" => return current,
+            parent => current = parent,
+        }
+    }
+}
+
+/// Child folder ids of each folder, `""` holding the top-level ones.
+pub fn children(directory: &BTreeMap<String, Directory>) -> BTreeMap<String, Vec<String>> {
+    let mut children: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for id in directory.keys() {
+        children
+            .entry(shown_parent(directory, id).to_owned())
+            .or_default()
+            .push(id.clone());
+    }
+    children
+}
+
+/// `id` and every folder shown below it.
+pub fn subtree(directory: &BTreeMap<String, Directory>, id: &str) -> Vec<String> {
+    let children = children(directory);
+    let mut ids = vec![id.to_owned()];
+    let mut i = 0;
+    while i < ids.len() {
+        ids.extend(children.get(&ids[i]).into_iter().flatten().cloned());
+        i += 1;
+    }
+    ids
+}
+
+/// Move the item at `from` so it ends up at index `to`.
+pub fn reorder<T>(items: &mut Vec<T>, from: usize, to: usize) {
+    if from >= items.len() {
+        return;
+    }
+    let item = items.remove(from);
+    items.insert(to.min(items.len()), item);
+}
+
+/// Move the request `id` out of folder `from` to before position `before`
+/// of folder `to`, which may be the same folder.
+pub fn move_location(
+    directory: &mut BTreeMap<String, Directory>,
+    from: &str,
+    id: &str,
+    to: &str,
+    before: usize,
+) {
+    let Some(index) = directory
+        .get(from)
+        .and_then(|dir| dir.locations.iter().position(|l| l == id))
+    else {
+        return;
+    };
+    if from == to {
+        // Taking the item out shifts the positions after it by one.
+        let to_index = if before > index { before - 1 } else { before };
+        if let Some(dir) = directory.get_mut(from) {
+            reorder(&mut dir.locations, index, to_index);
+        }
+    } else if directory.contains_key(to) {
+        let id = directory.get_mut(from).unwrap().locations.remove(index);
+        let locations = &mut directory.get_mut(to).unwrap().locations;
+        locations.insert(before.min(locations.len()), id);
+    }
+}
+
+/// `name`, or `name (2)`, `name (3)`... if one of `taken` already has it.
+pub fn unique_name<'a>(name: &str, taken: impl IntoIterator<Item = &'a str>) -> String {
+    let taken: BTreeSet<&str> = taken.into_iter().collect();
+    if !taken.contains(name) {
+        return name.to_owned();
+    }
+    (2..)
+        .map(|i| format!("{} ({})", name, i))
+        .find(|candidate| !taken.contains(candidate.as_str()))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(folders: &[(&str, &str)]) -> BTreeMap<String, Directory> {
+        folders
+            .iter()
+            .map(|(id, parent)| {
+                let dir = Directory {
+                    id: id.to_string(),
+                    parent: parent.to_string(),
+                    ..Directory::default()
+                };
+                (id.to_string(), dir)
+            })
+            .collect()
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn nests_folders_under_their_parents() {
+        let directory = tree(&[("a", ""), ("b", "a"), ("c", "b"), ("d", ""), ("e", "a")]);
+        let children = children(&directory);
+        assert_eq!(children[""], ids(&["a", "d"]));
+        assert_eq!(children["a"], ids(&["b", "e"]));
+        assert_eq!(children["b"], ids(&["c"]));
+        assert!(!children.contains_key("c"));
+        assert_eq!(subtree(&directory, "a"), ids(&["a", "b", "e", "c"]));
+        assert_eq!(subtree(&directory, "d"), ids(&["d"]));
+    }
+
+    #[test]
+    fn orphans_and_cycles_go_to_the_top() {
+        let directory = tree(&[("a", "gone"), ("b", "c"), ("c", "b"), ("d", "b")]);
+        let children = children(&directory);
+        assert_eq!(children[""], ids(&["a", "b", "c"]));
+        // `d` hangs off the cycle, which is shown at the top.
+        assert_eq!(children["b"], ids(&["d"]));
+    }
+
+    #[test]
+    fn roots_are_the_top_level_folders() {
+        let directory = tree(&[("a", ""), ("b", "a"), ("c", "b"), ("d", "gone")]);
+        assert_eq!(root(&directory, "c"), "a");
+        assert_eq!(root(&directory, "a"), "a");
+        assert_eq!(root(&directory, "d"), "d");
+        let cycle = tree(&[("b", "c"), ("c", "b"), ("d", "b")]);
+        assert_eq!(root(&cycle, "d"), "b");
+    }
+
+    #[test]
+    fn reorders_items() {
+        let mut items = vec![0, 1, 2, 3];
+        reorder(&mut items, 0, 2);
+        assert_eq!(items, [1, 2, 0, 3]);
+        reorder(&mut items, 3, 0);
+        assert_eq!(items, [3, 1, 2, 0]);
+        reorder(&mut items, 1, 10);
+        assert_eq!(items, [3, 2, 0, 1]);
+        reorder(&mut items, 4, 0);
+        assert_eq!(items, [3, 2, 0, 1]);
+    }
+
+    #[test]
+    fn moves_requests_within_and_between_folders() {
+        let mut directory = tree(&[("a", ""), ("b", "")]);
+        directory.get_mut("a").unwrap().locations = ids(&["x", "y", "z"]);
+        // Dropped before `z`: after `y`, where the position counts `x` still.
+        move_location(&mut directory, "a", "x", "a", 2);
+        assert_eq!(directory["a"].locations, ids(&["y", "x", "z"]));
+        move_location(&mut directory, "a", "z", "b", 5);
+        assert_eq!(directory["a"].locations, ids(&["y", "x"]));
+        assert_eq!(directory["b"].locations, ids(&["z"]));
+        move_location(&mut directory, "a", "y", "gone", 0);
+        assert_eq!(directory["a"].locations, ids(&["y", "x"]));
+    }
+}
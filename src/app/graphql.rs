@@ -0,0 +1,75 @@
+use serde_json::{Map, Value};
+
+/// The JSON POST body of a GraphQL request: `query`, then `variables` and
+/// `operationName` if given. `variables` must be a JSON object.
+pub fn body(query: &str, variables: &str, operation_name: &str) -> Result<String, String> {
+    let mut body = Map::new();
+    body.insert("query".to_owned(), Value::String(query.to_owned()));
+    if let Some(variables) = parse_variables(variables)? {
+        body.insert("variables".to_owned(), variables);
+    }
+    if !operation_name.trim().is_empty() {
+        body.insert(
+            "operationName".to_owned(),
+            Value::String(operation_name.trim().to_owned()),
+        );
+    }
+    Ok(Value::Object(body).to_string())
+}
+
+/// The variables as a JSON object, `None` if left empty.
+pub fn parse_variables(variables: &str) -> Result<Option<Value>, String> {
+    if variables.trim().is_empty() {
+        return Ok(None);
+    }
+    match serde_json::from_str::<Value>(variables) {
+        Ok(value @ Value::Object(_)) => Ok(Some(value)),
+        Ok(_) => Err("the variables must be a JSON object".to_owned()),
+        Err(err) => Err(format!("the variables aren't JSON: {}", err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parsed(body: Result<String, String>) -> Value {
+        serde_json::from_str(&body.unwrap()).unwrap()
+    }
+
+    #[test]
+    fn assembles_query_variables_and_operation_name() {
+        assert_eq!(
+            parsed(body(
+                "query User($id: ID!) { user(id: $id) { name } }",
+                r#"{"id": 7}"#,
+                " User ",
+            )),
+            json!({
+                "query": "query User($id: ID!) { user(id: $id) { name } }",
+                "variables": {"id": 7},
+                "operationName": "User",
+            })
+        );
+    }
+
+    #[test]
+    fn blank_variables_and_operation_name_are_omitted() {
+        assert_eq!(parse_variables(" \n"), Ok(None));
+        assert_eq!(
+            parsed(body("{ me { id } }", "", "")),
+            json!({"query": "{ me { id } }"})
+        );
+    }
+
+    #[test]
+    fn variables_must_be_a_json_object() {
+        let err = body("{ me { id } }", "{id: 7}", "").unwrap_err();
+        assert!(err.starts_with("the variables aren't JSON"), "{}", err);
+        assert_eq!(
+            parse_variables("[1]"),
+            Err("the variables must be a JSON object".to_owned())
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use uuid::Uuid;
+
+use super::{query, ContentType, Location, Method};
+
+/// The parts of a HAR archive (as exported by browser dev tools) that make
+/// up the requests.
+#[derive(serde::Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(serde::Deserialize)]
+struct Log {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(serde::Deserialize)]
+struct Entry {
+    request: Request,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<Pair>,
+    #[serde(default)]
+    query_string: Vec<Pair>,
+    post_data: Option<PostData>,
+}
+
+#[derive(serde::Deserialize)]
+struct Pair {
+    name: String,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostData {
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    params: Vec<Pair>,
+}
+
+/// Headers the client computes itself; HTTP/2 pseudo-headers are dropped too.
+const SKIPPED_HEADERS: &[&str] = &["content-length", "host", "connection"];
+
+/// One request per HTTP entry of a HAR archive. Entries for other schemes
+/// (`ws:`, `data:`, browser extensions, ...) are skipped.
+pub fn parse(text: &str) -> Result<Vec<Location>, String> {
+    let har: Har = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    Ok(har
+        .log
+        .entries
+        .into_iter()
+        .filter_map(|entry| location(entry.request))
+        .collect())
+}
+
+fn pairs(pairs: Vec<Pair>) -> Vec<(String, String)> {
+    pairs.into_iter().map(|p| (p.name, p.value)).collect()
+}
+
+fn location(request: Request) -> Option<Location> {
+    let scheme = request.url.split_once("://")?.0.to_ascii_lowercase();
+    if scheme != "http" && scheme != "https" {
+        return None;
+    }
+    let mut url = request.url;
+    let mut params = Vec::new();
+    query::absorb(&mut url, &mut params);
+    // The archive's own list has the values decoded already.
+    if !request.query_string.is_empty() {
+        params = pairs(request.query_string);
+    }
+    let header = request
+        .headers
+        .into_iter()
+        .filter(|h| {
+            !h.name.starts_with(':') && !SKIPPED_HEADERS.contains(&h.name.to_lowercase().as_str())
+        })
+        .map(|h| (h.name, h.value))
+        .collect();
+
+    let mut location = Location {
+        id: Uuid::new_v4().to_string(),
+        name: format!("{} {}", request.method.to_uppercase(), url),
+        url,
+        method: Method::from_text(request.method),
+        params,
+        header,
+        ..Default::default()
+    };
+    if let Some(post_data) = request.post_data {
+        let mime_type = post_data.mime_type.to_ascii_lowercase();
+        if mime_type.starts_with("application/x-www-form-urlencoded") {
+            location.content_type = ContentType::FormUrlEncoded;
+            location.form_params = if post_data.params.is_empty() {
+                post_data
+                    .text
+                    .split('&')
+                    .filter(|pair| !pair.is_empty())
+                    .map(|pair| match pair.split_once('=') {
+                        Some((k, v)) => (k.to_owned(), v.to_owned()),
+                        None => (pair.to_owned(), "".to_owned()),
+                    })
+                    .collect()
+            } else {
+                pairs(post_data.params)
+            };
+        } else if mime_type.starts_with("multipart/form-data") && !post_data.params.is_empty() {
+            location.content_type = ContentType::FormData;
+            location.form_params = pairs(post_data.params);
+            // The boundary in the recorded header wouldn't match ours.
+            location
+                .header
+                .retain(|(k, _)| !k.eq_ignore_ascii_case("Content-Type"));
+        } else {
+            if !mime_type.contains("json") {
+                location.content_type = ContentType::Raw;
+                location.raw_content_type = post_data.mime_type;
+            }
+            location.body = post_data.text;
+        }
+    }
+    Some(location)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_http_entries() {
+        let har = serde_json::json!({"log": {"entries": [
+            {"request": {
+                "method": "GET",
+                "url": "https://api.example/items?q=a%20b",
+                "headers": [
+                    {"name": ":authority", "value": "api.example"},
+                    {"name": "Host", "value": "api.example"},
+                    {"name": "Accept", "value": "application/json"},
+                ],
+                "queryString": [{"name": "q", "value": "a b"}],
+            }},
+            {"request": {"method": "GET", "url": "wss://api.example/live"}},
+            {"request": {
+                "method": "POST",
+                "url": "https://api.example/login",
+                "postData": {
+                    "mimeType": "application/x-www-form-urlencoded",
+                    "text": "user=ann&remember",
+                },
+            }},
+        ]}});
+        let locations = parse(&har.to_string()).unwrap();
+        assert_eq!(locations.len(), 2);
+
+        let get = &locations[0];
+        assert_eq!(get.name, "GET https://api.example/items");
+        assert_eq!(get.params, vec![("q".to_owned(), "a b".to_owned())]);
+        assert_eq!(
+            get.header,
+            vec![("Accept".to_owned(), "application/json".to_owned())]
+        );
+
+        let post = &locations[1];
+        assert_eq!(post.method, Method::Post);
+        assert_eq!(post.content_type, ContentType::FormUrlEncoded);
+        assert_eq!(
+            post.form_params,
+            vec![
+                ("user".to_owned(), "ann".to_owned()),
+                ("remember".to_owned(), String::new())
+            ]
+        );
+        assert!(parse("{}").is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{Location, Resource};
+
+/// Entries kept; the oldest ones go first.
+pub const MAX_ENTRIES: usize = 100;
+
+#[derive(Debug, PartialEq, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct HistoryEntry {
+    /// Unix time in seconds when the response arrived.
+    pub timestamp: u64,
+    /// The request as it was sent, with variables already resolved.
+    pub location: Location,
+    pub status: usize,
+    /// The response as it was received.
+    pub response: Resource,
+}
+
+#[derive(Debug, PartialEq, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+impl History {
+    pub fn push(&mut self, location: Location, response: &Resource) {
+        self.entries.push(HistoryEntry {
+            timestamp: now(),
+            location,
+            status: response.status,
+            response: response.clone(),
+        });
+        self.truncate();
+    }
+
+    /// Drop the oldest entries beyond `MAX_ENTRIES`.
+    pub fn truncate(&mut self) {
+        let excess = self.entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries.drain(..excess);
+    }
+
+    /// Drop entries older than `max_age_days`; `0` keeps everything.
+    pub fn prune(&mut self, max_age_days: u64) {
+        if max_age_days == 0 {
+            return;
+        }
+        let cutoff = now().saturating_sub(max_age_days * 24 * 60 * 60);
+        self.entries.retain(|e| e.timestamp >= cutoff);
+    }
+}
+
+/// How long ago `timestamp` was, roughly: "12s ago", "5 min ago", ...
+pub fn ago(timestamp: u64) -> String {
+    let seconds = now().saturating_sub(timestamp);
+    match seconds {
+        0..=59 => format!("{}s ago", seconds),
+        60..=3599 => format!("{} min ago", seconds / 60),
+        3600..=86399 => format!("{} h ago", seconds / 3600),
+        _ => format!("{} d ago", seconds / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sent(i: usize) -> Location {
+        Location {
+            url: format!("https://example.com/{}", i),
+            ..Location::default()
+        }
+    }
+
+    #[test]
+    fn evicts_the_oldest_entries_past_the_cap() {
+        let mut history = History::default();
+        for i in 0..MAX_ENTRIES + 5 {
+            let response = Resource {
+                status: 200 + i,
+                ..Resource::default()
+            };
+            history.push(sent(i), &response);
+        }
+        assert_eq!(history.entries.len(), MAX_ENTRIES);
+        assert_eq!(history.entries[0].location, sent(5));
+        assert_eq!(history.entries[0].status, 205);
+        assert_eq!(
+            history.entries.last().unwrap().location,
+            sent(MAX_ENTRIES + 4)
+        );
+    }
+
+    #[test]
+    fn prunes_by_age() {
+        let mut history = History::default();
+        history.push(sent(0), &Resource::default());
+        history.push(sent(1), &Resource::default());
+        history.entries[0].timestamp = now() - 3 * 24 * 60 * 60;
+        history.prune(0);
+        assert_eq!(history.entries.len(), 2);
+        history.prune(2);
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].location, sent(1));
+    }
+}
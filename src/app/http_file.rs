@@ -0,0 +1,82 @@
+use uuid::Uuid;
+
+use super::{ContentType, Location, Method};
+
+/// Parse a `.http` / `.rest` file as used by the VS Code REST Client and
+/// JetBrains HTTP client: requests separated by `###` lines, each made of a
+/// request line, headers, a blank line and an optional body.
+pub fn parse(text: &str) -> Vec<Location> {
+    let mut locations = Vec::new();
+    let mut name = String::new();
+    let mut block: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        if let Some(title) = line.trim_start().strip_prefix("###") {
+            locations.extend(parse_block(&name, &block));
+            name = title.trim().to_owned();
+            block.clear();
+        } else {
+            block.push(line);
+        }
+    }
+    locations.extend(parse_block(&name, &block));
+    locations
+}
+
+fn parse_block(name: &str, lines: &[&str]) -> Option<Location> {
+    let mut lines = lines.iter().map(|l| l.trim_end()).skip_while(|l| {
+        let l = l.trim();
+        l.is_empty() || l.starts_with('#') || l.starts_with("//")
+    });
+
+    let request_line = lines.next()?;
+    let mut parts = request_line.split_whitespace();
+    let first = parts.next()?;
+    let (method, url) = match parts.next() {
+        Some(url) => (Method::from_text(first.to_owned()), url),
+        None => (Method::Get, first),
+    };
+
+    let mut header = Vec::new();
+    for line in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            header.push((key.trim().to_owned(), value.trim().to_owned()));
+        }
+    }
+    let body = lines.collect::<Vec<&str>>().join("\n").trim().to_owned();
+
+    let form_encoded = header.iter().any(|(k, v)| {
+        k.eq_ignore_ascii_case("Content-Type") && v.contains("application/x-www-form-urlencoded")
+    });
+    let (content_type, form_params, body) = if form_encoded {
+        let form_params = body
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((k, v)) => (k.to_owned(), v.to_owned()),
+                None => (pair.to_owned(), "".to_owned()),
+            })
+            .collect();
+        (ContentType::FormUrlEncoded, form_params, String::new())
+    } else {
+        (ContentType::Json, Vec::new(), body)
+    };
+
+    Some(Location {
+        id: Uuid::new_v4().to_string(),
+        name: if name.is_empty() {
+            request_line.to_owned()
+        } else {
+            name.to_owned()
+        },
+        url: url.to_owned(),
+        method,
+        header,
+        body,
+        content_type,
+        form_params,
+        ..Default::default()
+    })
+}
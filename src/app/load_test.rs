@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use super::cookies::Jar;
+use super::{send_request, Location};
+
+/// Settings for repeating the current request.
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Repeat {
+    pub count: usize,
+    pub concurrency: usize,
+}
+
+impl Default for Repeat {
+    fn default() -> Self {
+        Self {
+            count: 10,
+            concurrency: 1,
+        }
+    }
+}
+
+struct Sample {
+    elapsed_ms: u128,
+    /// `None` if the request failed without a response.
+    status: Option<usize>,
+}
+
+/// Progress and results of a repeated run, shared with the worker threads.
+#[derive(Default)]
+pub struct LoadTest {
+    total: usize,
+    samples: Vec<Sample>,
+}
+
+impl LoadTest {
+    pub fn is_done(&self) -> bool {
+        self.samples.len() >= self.total
+    }
+}
+
+/// Fire `location` `repeat.count` times from `repeat.concurrency` threads.
+///
+/// Variables are resolved per request so dynamic values differ between runs.
+/// Cookies go to and come from `jar`.
+pub fn start(
+    agent: &ureq::Agent,
+    location: &Location,
+    jar: &Arc<Mutex<Jar>>,
+    variables: &BTreeMap<String, String>,
+    repeat: &Repeat,
+    ctx: &egui::Context,
+) -> Arc<Mutex<LoadTest>> {
+    let test = Arc::new(Mutex::new(LoadTest {
+        total: repeat.count,
+        samples: Vec::with_capacity(repeat.count),
+    }));
+    let remaining = Arc::new(AtomicUsize::new(repeat.count));
+    for _ in 0..repeat.concurrency.clamp(1, repeat.count.max(1)) {
+        let agent = agent.clone();
+        let location = location.clone();
+        let jar = jar.clone();
+        let variables = variables.clone();
+        let test = test.clone();
+        let remaining = remaining.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            while remaining
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                let resolved = location.resolve(&variables);
+                let start = Instant::now();
+                let status = send_request(&agent, &resolved, Some(&jar)).map(|r| r.status);
+                let elapsed_ms = start.elapsed().as_millis();
+                test.lock()
+                    .unwrap()
+                    .samples
+                    .push(Sample { elapsed_ms, status });
+                ctx.request_repaint();
+            }
+        });
+    }
+    test
+}
+
+pub fn ui_load_test(ui: &mut egui::Ui, test: &LoadTest) {
+    ui.monospace(format!("done:   {}/{}", test.samples.len(), test.total));
+    if test.samples.is_empty() {
+        return;
+    }
+
+    let mut latencies: Vec<u128> = test.samples.iter().map(|s| s.elapsed_ms).collect();
+    latencies.sort_unstable();
+    let n = latencies.len();
+    let avg = latencies.iter().sum::<u128>() / n as u128;
+    let p95 = latencies[((n as f32 * 0.95).ceil() as usize).clamp(1, n) - 1];
+    ui.monospace(format!(
+        "time:   min {} ms / max {} ms / avg {} ms / p95 {} ms",
+        latencies[0],
+        latencies[n - 1],
+        avg,
+        p95
+    ));
+
+    let mut statuses: BTreeMap<String, usize> = BTreeMap::new();
+    for sample in &test.samples {
+        let key = sample
+            .status
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "error".to_owned());
+        *statuses.entry(key).or_default() += 1;
+    }
+    let statuses: Vec<String> = statuses
+        .iter()
+        .map(|(status, count)| format!("{}×{}", status, count))
+        .collect();
+    ui.monospace(format!("status: {}", statuses.join("  ")));
+}
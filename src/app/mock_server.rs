@@ -0,0 +1,129 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use super::Example;
+
+/// An example response served for requests matching `method` and `path`.
+#[derive(Clone)]
+pub struct Route {
+    pub method: String,
+    pub path: String,
+    pub example: Example,
+}
+
+/// A small HTTP/1.1 server answering with the example responses of saved requests.
+pub struct MockServer {
+    pub port: u16,
+    pub routes: Arc<Mutex<Vec<Route>>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl MockServer {
+    pub fn start(port: u16, routes: Vec<Route>) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        listener.set_nonblocking(true)?;
+        let routes = Arc::new(Mutex::new(routes));
+        let stop = Arc::new(AtomicBool::new(false));
+        {
+            let routes = routes.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            let routes = routes.clone();
+                            thread::spawn(move || handle(stream, &routes));
+                        }
+                        Err(_) => thread::sleep(Duration::from_millis(50)),
+                    }
+                }
+            });
+        }
+        Ok(Self { port, routes, stop })
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The path part of a request URL, without scheme, host or query string.
+pub fn url_path(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = if url.contains("://") {
+        without_scheme
+            .find('/')
+            .map_or("/", |i| &without_scheme[i..])
+    } else {
+        without_scheme
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    if path.starts_with('/') {
+        path.to_owned()
+    } else {
+        format!("/{}", path)
+    }
+}
+
+fn handle(stream: TcpStream, routes: &Mutex<Vec<Route>>) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let mut reader = BufReader::new(&stream);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_uppercase();
+    let path = url_path(parts.next().unwrap_or("/"));
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            if key.trim().eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+
+    let route = routes
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|r| r.method == method && r.path == path)
+        .cloned();
+    let (status, content_type, body) = match route {
+        Some(route) => (
+            route.example.status,
+            route.example.content_type,
+            route.example.body,
+        ),
+        None => (
+            404,
+            "text/plain".to_owned(),
+            format!("no example response for {} {}", method, path),
+        ),
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        crate::http_status::explain(status as usize).map_or("", |(reason, _)| reason),
+        content_type,
+        body.len(),
+        body
+    );
+    let _ = (&stream).write_all(response.as_bytes());
+}
@@ -0,0 +1,135 @@
+use std::io::Write;
+use std::path::Path;
+
+use uuid::Uuid;
+
+/// Fields whose value starts with this are uploaded as the file at the rest
+/// of the value, as with `curl -F name=@path`.
+pub const FILE_PREFIX: char = '@';
+
+fn mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "json" => "application/json",
+        "txt" | "log" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "xml" => "application/xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Escape a name for a quoted `Content-Disposition` parameter.
+fn quote(name: &str) -> String {
+    name.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Encode `fields` as a `multipart/form-data` body. Returns the
+/// `Content-Type` header value, which carries the boundary, and the body.
+/// Fields with an empty name are left out; a file that can't be read fails
+/// the whole body.
+pub fn encode(fields: &[(String, String)]) -> std::io::Result<(String, Vec<u8>)> {
+    let boundary = format!("----orient{}", Uuid::new_v4().simple());
+    let mut body = Vec::new();
+    for (name, value) in fields.iter().filter(|(name, _)| !name.is_empty()) {
+        write!(body, "--{}\r\n", boundary)?;
+        match value.strip_prefix(FILE_PREFIX) {
+            Some(path) => {
+                let path = Path::new(path.trim());
+                let contents = std::fs::read(path).map_err(|err| {
+                    std::io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+                })?;
+                let filename = path
+                    .file_name()
+                    .map(|f| f.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                write!(
+                    body,
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+                     Content-Type: {}\r\n\r\n",
+                    quote(name),
+                    quote(&filename),
+                    mime_type(path)
+                )?;
+                body.extend_from_slice(&contents);
+            }
+            None => {
+                write!(
+                    body,
+                    "Content-Disposition: form-data; name=\"{}\"\r\n\r\n{}",
+                    quote(name),
+                    value
+                )?;
+            }
+        }
+        body.extend_from_slice(b"\r\n");
+    }
+    write!(body, "--{}--\r\n", boundary)?;
+    Ok((format!("multipart/form-data; boundary={}", boundary), body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn encodes_text_fields_and_files() {
+        let dir = std::env::temp_dir().join(format!("orient-{}", Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+        let path = dir.join("logo.png");
+        let bytes = [0x89, b'P', b'N', b'G', 0, 0xff, b'\r', b'\n'];
+        std::fs::write(&path, bytes).unwrap();
+
+        let (content_type, body) = encode(&pairs(&[
+            ("title", "a \"quoted\" name"),
+            ("", "dropped"),
+            ("logo", &format!("@ {}", path.display())),
+        ]))
+        .unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        let mut expected = format!(
+            "--{b}\r\n\
+             Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+             a \"quoted\" name\r\n\
+             --{b}\r\n\
+             Content-Disposition: form-data; name=\"logo\"; filename=\"logo.png\"\r\n\
+             Content-Type: image/png\r\n\r\n",
+            b = boundary
+        )
+        .into_bytes();
+        expected.extend_from_slice(&bytes);
+        expected.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+        assert_eq!(body, expected);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_files_fail_the_body() {
+        let err = encode(&pairs(&[("file", "@/nonexistent/orient.bin")])).unwrap_err();
+        assert!(
+            err.to_string().starts_with("/nonexistent/orient.bin: "),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,350 @@
+use openapiv3::{
+    APIKeyLocation, OpenAPI, Operation, Parameter, ReferenceOr, RequestBody, SecurityRequirement,
+    SecurityScheme,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::{ApiKeyPlacement, Auth, ContentType, Location, Method};
+use crate::{sample, yaml};
+
+/// Whether `text` is an OpenAPI 3 document rather than some other JSON file.
+pub fn is_spec(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text)
+        .map(|value| value.get("openapi").is_some())
+        .unwrap_or(false)
+}
+
+/// Parse a JSON or YAML OpenAPI 3 document.
+fn load(text: &str) -> Result<OpenAPI, String> {
+    match serde_json::from_str(text) {
+        Ok(spec) => Ok(spec),
+        Err(_) => serde_json::from_value(yaml::to_json(text)?).map_err(|err| err.to_string()),
+    }
+}
+
+/// The API title and one request per path and method.
+pub fn parse(text: &str) -> Result<(String, Vec<Location>), String> {
+    let spec = load(text)?;
+    // Schemas are sampled from the document as JSON, where their `$ref`s
+    // point into.
+    let root = serde_json::to_value(&spec).map_err(|err| err.to_string())?;
+    let server = spec
+        .servers
+        .first()
+        .map(|server| {
+            let mut url = server.url.trim_end_matches('/').to_owned();
+            for (name, variable) in server.variables.iter().flatten() {
+                url = url.replace(&format!("{{{}}}", name), &variable.default);
+            }
+            url
+        })
+        .unwrap_or_default();
+    let mut locations = Vec::new();
+    for (path, item) in spec.paths.iter() {
+        let ReferenceOr::Item(item) = item else {
+            continue;
+        };
+        for (method, operation) in item.iter() {
+            let requirements = operation.security.as_ref().or(spec.security.as_ref());
+            let mut location = Location {
+                id: Uuid::new_v4().to_string(),
+                name: operation
+                    .summary
+                    .clone()
+                    .or_else(|| operation.operation_id.clone())
+                    .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path)),
+                url: format!("{}{}", server, path),
+                method: Method::from_text(method.to_owned()),
+                auth: requirements
+                    .map(|requirements| auth(&spec, requirements))
+                    .unwrap_or_default(),
+                ..Default::default()
+            };
+            // Operation parameters override path ones of the same name.
+            let mut parameters: Vec<&Parameter> = Vec::new();
+            for parameter in item.parameters.iter().chain(&operation.parameters) {
+                let Some(parameter) = resolve_parameter(&spec, parameter) else {
+                    continue;
+                };
+                let name = &parameter.parameter_data_ref().name;
+                parameters.retain(|p| &p.parameter_data_ref().name != name);
+                parameters.push(parameter);
+            }
+            for parameter in parameters {
+                let data = parameter.parameter_data_ref();
+                let value = data.example.as_ref().map(plain).unwrap_or_default();
+                match parameter {
+                    Parameter::Path { .. } => {
+                        location.url = location.url.replace(
+                            &format!("{{{}}}", data.name),
+                            &format!("{{{{{}}}}}", data.name),
+                        );
+                    }
+                    Parameter::Query { .. } => location.params.push((data.name.clone(), value)),
+                    Parameter::Header { .. } => location.header.push((data.name.clone(), value)),
+                    Parameter::Cookie { .. } => location
+                        .header
+                        .push(("Cookie".to_owned(), format!("{}={}", data.name, value))),
+                }
+            }
+            set_body(&mut location, &spec, &root, operation);
+            locations.push(location);
+        }
+    }
+    Ok((spec.info.title, locations))
+}
+
+/// Parameter values as typed into the editor: strings without quotes.
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// The item `reference` stands for, following `#/components/<kind>/<name>`
+/// references through `lookup`.
+fn resolve<'a, T>(
+    reference: &'a ReferenceOr<T>,
+    kind: &str,
+    lookup: impl Fn(&str) -> Option<&'a ReferenceOr<T>>,
+) -> Option<&'a T> {
+    let mut current = reference;
+    // References to references are allowed, but not endless ones.
+    for _ in 0..8 {
+        match current {
+            ReferenceOr::Item(item) => return Some(item),
+            ReferenceOr::Reference { reference } => {
+                let prefix = format!("#/components/{}/", kind);
+                current = lookup(reference.strip_prefix(&prefix)?)?;
+            }
+        }
+    }
+    None
+}
+
+fn resolve_parameter<'a>(
+    spec: &'a OpenAPI,
+    parameter: &'a ReferenceOr<Parameter>,
+) -> Option<&'a Parameter> {
+    resolve(parameter, "parameters", |name| {
+        spec.components.as_ref()?.parameters.get(name)
+    })
+}
+
+fn resolve_request_body<'a>(
+    spec: &'a OpenAPI,
+    body: &'a ReferenceOr<RequestBody>,
+) -> Option<&'a RequestBody> {
+    resolve(body, "requestBodies", |name| {
+        spec.components.as_ref()?.request_bodies.get(name)
+    })
+}
+
+/// Fill in the request body from the operation's example, or else a sample
+/// of its schema. JSON bodies are preferred over form ones.
+fn set_body(location: &mut Location, spec: &OpenAPI, root: &Value, operation: &Operation) {
+    let Some(body) = operation
+        .request_body
+        .as_ref()
+        .and_then(|body| resolve_request_body(spec, body))
+    else {
+        return;
+    };
+    let media = [
+        "application/json",
+        "application/x-www-form-urlencoded",
+        "multipart/form-data",
+    ]
+    .into_iter()
+    .find_map(|media_type| Some((media_type, body.content.get(media_type)?)))
+    .or_else(|| {
+        body.content
+            .iter()
+            .find(|(media_type, _)| media_type.ends_with("+json"))
+            .map(|(media_type, media)| (media_type.as_str(), media))
+    });
+    let Some((media_type, media)) = media else {
+        return;
+    };
+    let example = media.example.clone().or_else(|| {
+        let schema = serde_json::to_value(media.schema.as_ref()?).ok()?;
+        Some(sample::from_schema_in(root, &schema))
+    });
+    let Some(example) = example else {
+        return;
+    };
+    match media_type {
+        "application/x-www-form-urlencoded" | "multipart/form-data" => {
+            location.content_type = if media_type == "multipart/form-data" {
+                ContentType::FormData
+            } else {
+                ContentType::FormUrlEncoded
+            };
+            if let Value::Object(fields) = example {
+                location.form_params = fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), plain(value)))
+                    .collect();
+            }
+        }
+        _ => {
+            location.content_type = ContentType::Json;
+            location.body = serde_json::to_string_pretty(&example).unwrap_or_default();
+        }
+    }
+}
+
+/// Auth for the first scheme of the first requirement that we can express.
+/// Secrets become `{{variable}}` placeholders named after the scheme, so
+/// they're filled in once per environment.
+fn auth(spec: &OpenAPI, requirements: &[SecurityRequirement]) -> Auth {
+    let schemes = spec.components.as_ref().map(|c| &c.security_schemes);
+    for requirement in requirements {
+        // An empty requirement makes authentication optional.
+        if requirement.is_empty() {
+            return Auth::None;
+        }
+        for name in requirement.keys() {
+            let Some(ReferenceOr::Item(scheme)) = schemes.and_then(|s| s.get(name)) else {
+                continue;
+            };
+            if let Some(auth) = scheme_auth(name, scheme) {
+                return auth;
+            }
+        }
+    }
+    Auth::None
+}
+
+fn scheme_auth(name: &str, scheme: &SecurityScheme) -> Option<Auth> {
+    let placeholder = |suffix: &str| format!("{{{{{}{}}}}}", name, suffix);
+    Some(match scheme {
+        SecurityScheme::APIKey {
+            location,
+            name: key,
+            ..
+        } => match location {
+            APIKeyLocation::Header => Auth::ApiKey {
+                key: key.clone(),
+                value: placeholder(""),
+                placement: ApiKeyPlacement::Header,
+            },
+            APIKeyLocation::Query => Auth::ApiKey {
+                key: key.clone(),
+                value: placeholder(""),
+                placement: ApiKeyPlacement::Query,
+            },
+            APIKeyLocation::Cookie => Auth::ApiKey {
+                key: "Cookie".to_owned(),
+                value: format!("{}={}", key, placeholder("")),
+                placement: ApiKeyPlacement::Header,
+            },
+        },
+        SecurityScheme::HTTP { scheme, .. } => match scheme.to_ascii_lowercase().as_str() {
+            "basic" => Auth::Basic {
+                username: placeholder("_username"),
+                password: placeholder("_password"),
+            },
+            "bearer" => Auth::Bearer {
+                token: placeholder(""),
+            },
+            _ => return None,
+        },
+        // The access token ends up as a bearer token either way.
+        SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. } => Auth::Bearer {
+            token: placeholder(""),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Pet Store
+  version: "1"
+servers:
+  - url: https://{region}.pets.example/v1/
+    variables:
+      region:
+        default: eu
+security:
+  - token: []
+paths:
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        required: true
+        schema: {type: integer}
+    get:
+      summary: Get a pet
+      responses: {}
+      parameters:
+        - name: fields
+          in: query
+          example: name,tag
+          schema: {type: string}
+        - name: X-Trace
+          in: header
+          example: 7
+          schema: {type: integer}
+  /pets:
+    post:
+      operationId: addPet
+      responses: {}
+      security: []
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name: {type: string, example: Rex}
+  securitySchemes:
+    token:
+      type: http
+      scheme: bearer
+"#;
+
+    #[test]
+    fn imports_paths_as_requests() {
+        assert!(!is_spec("{\"info\": {}}"));
+        let (title, locations) = parse(SPEC).unwrap();
+        assert_eq!(title, "Pet Store");
+        assert_eq!(locations.len(), 2);
+
+        let get = locations.iter().find(|l| l.method == Method::Get).unwrap();
+        assert_eq!(get.name, "Get a pet");
+        assert_eq!(get.url, "https://eu.pets.example/v1/pets/{{petId}}");
+        assert_eq!(
+            get.params,
+            vec![("fields".to_owned(), "name,tag".to_owned())]
+        );
+        assert_eq!(get.header, vec![("X-Trace".to_owned(), "7".to_owned())]);
+        assert_eq!(
+            get.auth,
+            Auth::Bearer {
+                token: "{{token}}".to_owned()
+            }
+        );
+
+        let post = locations.iter().find(|l| l.method == Method::Post).unwrap();
+        assert_eq!(post.name, "addPet");
+        assert_eq!(post.content_type, ContentType::Json);
+        assert_eq!(
+            serde_json::from_str::<Value>(&post.body).unwrap(),
+            serde_json::json!({"name": "Rex"})
+        );
+        assert_eq!(post.auth, Auth::None);
+    }
+}
@@ -0,0 +1,142 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+
+use egui_extras::RetainedImage;
+use uuid::Uuid;
+
+pub const PDF_CONTENT_TYPE: &str = "application/pdf";
+
+/// Whether bodies of `content_type` are binary data, kept as bytes rather
+/// than text.
+pub fn is_binary(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let Some((kind, subtype)) = essence.split_once('/') else {
+        return false;
+    };
+    match kind {
+        "image" => subtype != "svg+xml",
+        "audio" | "video" | "font" => true,
+        "application" => {
+            matches!(
+                subtype,
+                "octet-stream"
+                    | "pdf"
+                    | "zip"
+                    | "gzip"
+                    | "x-gzip"
+                    | "x-tar"
+                    | "x-7z-compressed"
+                    | "x-protobuf"
+                    | "protobuf"
+                    | "wasm"
+                    | "msword"
+            ) || subtype.starts_with("vnd.ms-")
+                || subtype.starts_with("vnd.openxmlformats")
+        }
+        _ => false,
+    }
+}
+
+/// Whether bodies of `content_type` are raster images we can show.
+pub fn is_image(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    matches!(
+        essence.as_str(),
+        "image/png"
+            | "image/jpeg"
+            | "image/jpg"
+            | "image/gif"
+            | "image/webp"
+            | "image/bmp"
+            | "image/x-icon"
+            | "image/vnd.microsoft.icon"
+            | "image/tiff"
+    )
+}
+
+/// Decode an image body into something egui can draw.
+pub fn load_image(bytes: &[u8]) -> Result<RetainedImage, String> {
+    let image = image::load_from_memory(bytes).map_err(|err| err.to_string())?;
+    let size = [image.width() as usize, image.height() as usize];
+    let pixels = image.to_rgba8();
+    let image = egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_flat_samples().as_slice());
+    Ok(RetainedImage::from_color_image("response", image))
+}
+
+/// The version from a PDF's `%PDF-x.y` header, or `None` if the bytes don't
+/// start with one.
+pub fn pdf_version(bytes: &[u8]) -> Option<String> {
+    let rest = bytes.strip_prefix(b"%PDF-")?;
+    let version = rest
+        .iter()
+        .take_while(|b| b.is_ascii_digit() || **b == b'.')
+        .map(|b| *b as char)
+        .collect::<String>();
+    Some(version)
+}
+
+/// Write `bytes` to a temporary file and hand it to the platform's default viewer.
+pub fn open_in_system_viewer(bytes: &[u8], extension: &str) -> std::io::Result<PathBuf> {
+    let path = std::env::temp_dir().join(format!("orient-{}.{}", Uuid::new_v4(), extension));
+    std::fs::File::create(&path)?.write_all(bytes)?;
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = Command::new("xdg-open");
+
+    command.arg(&path).spawn()?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_content_types() {
+        assert!(is_binary("image/png"));
+        assert!(is_binary("application/octet-stream; charset=binary"));
+        assert!(is_binary(
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        ));
+        assert!(is_binary("Video/MP4"));
+        assert!(!is_binary("image/svg+xml"));
+        assert!(!is_binary("application/json"));
+        assert!(!is_binary("text/plain"));
+        assert!(!is_binary(""));
+    }
+
+    #[test]
+    fn image_content_types() {
+        assert!(is_image("image/png"));
+        assert!(is_image("IMAGE/JPEG; q=1"));
+        assert!(!is_image("image/svg+xml"));
+        assert!(!is_image("text/plain"));
+        assert!(!is_image("application/json"));
+        assert!(load_image(b"not an image").is_err());
+    }
+
+    #[test]
+    fn reads_pdf_versions() {
+        assert_eq!(pdf_version(b"%PDF-1.7\n%...").as_deref(), Some("1.7"));
+        assert_eq!(pdf_version(b"<html>"), None);
+    }
+}
@@ -0,0 +1,139 @@
+use super::cookies::percent_decode;
+
+/// Decode a query component: `+` is a space, then `%XX` escapes.
+fn decode(component: &str) -> String {
+    percent_decode(&component.replace('+', " "))
+}
+
+/// Split `url` into the URL without its query string and the decoded query
+/// pairs, in order. Repeated keys are kept, a key without `=` gets an empty
+/// value and a `#fragment` stays on the URL.
+pub fn split(url: &str) -> (String, Vec<(String, String)>) {
+    let Some((base, rest)) = url.split_once('?') else {
+        return (url.to_owned(), Vec::new());
+    };
+    let (query, fragment) = match rest.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (rest, None),
+    };
+    let params = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (decode(key), decode(value)),
+            None => (decode(pair), String::new()),
+        })
+        .collect();
+    let base = match fragment {
+        Some(fragment) => format!("{}#{}", base, fragment),
+        None => base.to_owned(),
+    };
+    (base, params)
+}
+
+/// Move the query string of `url` into `params`, after the ones already
+/// there. Returns whether there was one.
+pub fn absorb(url: &mut String, params: &mut Vec<(String, String)>) -> bool {
+    if !url.contains('?') {
+        return false;
+    }
+    let (base, pairs) = split(url);
+    *url = base;
+    // Drop the empty row the editor keeps around before appending.
+    params.retain(|(k, v)| !k.is_empty() || !v.is_empty());
+    params.extend(pairs);
+    true
+}
+
+/// Percent-encode a query component, leaving `{{variable}}` braces readable.
+fn encode(component: &str) -> String {
+    let mut out = String::with_capacity(component.len());
+    for byte in component.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'{' | b'}' => {
+                out.push(byte as char)
+            }
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// `url` with `params` appended as its query string; the inverse of `split`.
+pub fn join(url: &str, params: &[(String, String)]) -> String {
+    let query: Vec<String> = params
+        .iter()
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+        .collect();
+    if query.is_empty() {
+        return url.to_owned();
+    }
+    let (url, fragment) = match url.split_once('#') {
+        Some((url, fragment)) => (url, format!("#{}", fragment)),
+        None => (url, String::new()),
+    };
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}{}{}", url, separator, query.join("&"), fragment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn splits_encoded_empty_and_repeated_params() {
+        assert_eq!(
+            split("https://host/a?q=caf%C3%A9+au+lait&tag=x&tag=y&flag&empty=#top"),
+            (
+                "https://host/a#top".to_owned(),
+                pairs(&[
+                    ("q", "café au lait"),
+                    ("tag", "x"),
+                    ("tag", "y"),
+                    ("flag", ""),
+                    ("empty", ""),
+                ])
+            )
+        );
+        assert_eq!(
+            split("https://host/a"),
+            ("https://host/a".to_owned(), vec![])
+        );
+        assert_eq!(
+            split("https://host/a?"),
+            ("https://host/a".to_owned(), vec![])
+        );
+    }
+
+    #[test]
+    fn absorbs_after_existing_params() {
+        let mut url = "https://host/a?b=2".to_owned();
+        let mut params = pairs(&[("a", "1"), ("", "")]);
+        assert!(absorb(&mut url, &mut params));
+        assert_eq!(url, "https://host/a");
+        assert_eq!(params, pairs(&[("a", "1"), ("b", "2")]));
+        assert!(!absorb(&mut url, &mut params));
+    }
+
+    #[test]
+    fn join_is_the_inverse_of_split() {
+        let params = pairs(&[("q", "café au lait"), ("id", "{{id}}"), ("", "dropped")]);
+        let url = join("https://host/a#top", &params);
+        assert_eq!(url, "https://host/a?q=caf%C3%A9+au+lait&id={{id}}#top");
+        assert_eq!(split(&url).1, params[..2]);
+        assert_eq!(
+            join("https://host/a?x=1", &params[1..2]),
+            "https://host/a?x=1&id={{id}}"
+        );
+        assert_eq!(join("https://host/a", &[]), "https://host/a");
+    }
+}
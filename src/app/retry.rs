@@ -0,0 +1,236 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::Resource;
+
+/// When to send a request again after a 429 or 5xx answer.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Retry {
+    /// Attempts after the first; 0 turns retrying off.
+    pub max_retries: u32,
+    /// Wait before each retry if the answer has no `Retry-After`, doubled
+    /// every time.
+    pub delay_ms: u64,
+    /// Give up instead of waiting longer than this in total.
+    pub max_wait_ms: u64,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            delay_ms: 1_000,
+            max_wait_ms: 30_000,
+        }
+    }
+}
+
+/// Whether an answer with `status` is worth another attempt.
+pub fn retryable(status: usize) -> bool {
+    status == 429 || (500..=599).contains(&status)
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Seconds since the epoch of an HTTP date such as
+/// `Wed, 21 Oct 2015 07:28:00 GMT`.
+fn parse_http_date(date: &str) -> Option<u64> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let mut parts = date.split_once(',')?.1.split_whitespace();
+    let day: i64 = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|m| *m == month)? as i64 + 1;
+    let year: i64 = parts.next()?.parse().ok()?;
+    let mut time = parts.next()?.split(':').map(|n| n.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second;
+    u64::try_from(seconds).ok()
+}
+
+/// How long `Retry-After` in `headers` asks to wait: a number of seconds or
+/// a date, compared with `now`.
+pub fn retry_after(headers: &[(String, String)], now: SystemTime) -> Option<Duration> {
+    let (_, value) = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("Retry-After"))?;
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = UNIX_EPOCH + Duration::from_secs(parse_http_date(value)?);
+    Some(at.duration_since(now).unwrap_or_default())
+}
+
+/// Call `send` until it answers with something other than a retryable
+/// status, the retries run out or the next wait would go past
+/// `policy.max_wait_ms`. Waits end early once `cancelled` is set. The last
+/// answer is returned with the number of retries it took.
+pub fn send(
+    policy: &Retry,
+    cancelled: &AtomicBool,
+    mut send: impl FnMut() -> Option<Resource>,
+) -> Option<Resource> {
+    let mut waited = Duration::ZERO;
+    let mut retries = 0;
+    loop {
+        let mut resource = send()?;
+        resource.retries = retries;
+        if retries >= policy.max_retries || !retryable(resource.status) {
+            return Some(resource);
+        }
+        let delay = retry_after(&resource.headers, SystemTime::now()).unwrap_or_else(|| {
+            Duration::from_millis(policy.delay_ms.saturating_mul(1 << retries.min(16)))
+        });
+        let max_wait = Duration::from_millis(policy.max_wait_ms);
+        if waited
+            .checked_add(delay)
+            .is_none_or(|total| total > max_wait)
+        {
+            return Some(resource);
+        }
+        log::info!(
+            "{} answered {}, retrying in {} ms",
+            resource.url,
+            resource.status,
+            delay.as_millis()
+        );
+        let until = Instant::now() + delay;
+        while Instant::now() < until {
+            if cancelled.load(Ordering::Relaxed) {
+                return Some(resource);
+            }
+            thread::sleep(
+                Duration::from_millis(100).min(until.saturating_duration_since(Instant::now())),
+            );
+        }
+        waited += delay;
+        retries += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::mpsc::Receiver;
+
+    use super::super::test_server::{self, Request};
+    use super::super::{build_agent, send_request, Location};
+    use super::*;
+
+    fn headers(value: &str) -> Vec<(String, String)> {
+        vec![("retry-after".to_owned(), value.to_owned())]
+    }
+
+    #[test]
+    fn retry_after_seconds() {
+        let now = SystemTime::now();
+        assert_eq!(
+            retry_after(&headers(" 120 "), now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(retry_after(&[], now), None);
+        assert_eq!(retry_after(&headers("soon"), now), None);
+    }
+
+    #[test]
+    fn retry_after_date() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_445_412_470);
+        assert_eq!(
+            retry_after(&headers("Wed, 21 Oct 2015 07:28:00 GMT"), now),
+            Some(Duration::from_secs(10))
+        );
+        // A date in the past means no wait.
+        assert_eq!(
+            retry_after(&headers("Thu, 01 Jan 1970 00:00:00 GMT"), now),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        assert!(retryable(429));
+        assert!(retryable(503));
+        assert!(!retryable(404));
+        assert!(!retryable(0));
+    }
+
+    #[test]
+    fn huge_retry_after_gives_up() {
+        let policy = Retry {
+            max_retries: 3,
+            delay_ms: 1,
+            ..Retry::default()
+        };
+        let mut calls = 0;
+        let resource = send(&policy, &AtomicBool::new(false), || {
+            calls += 1;
+            // The second wait would overflow once added to the first.
+            Some(Resource {
+                status: 503,
+                headers: match calls {
+                    1 => Vec::new(),
+                    _ => headers(&u64::MAX.to_string()),
+                },
+                ..Resource::default()
+            })
+        });
+        assert_eq!(calls, 2);
+        assert_eq!(resource.map(|r| r.retries), Some(1));
+    }
+
+    /// Answers with `statuses` in turn, each with `Retry-After: 0`; the
+    /// receiver gets the requests.
+    fn server(statuses: &'static [&'static str]) -> (String, Receiver<Request>) {
+        let answered = AtomicUsize::new(0);
+        test_server::serve(move |_| {
+            let status = statuses[answered.fetch_add(1, Ordering::SeqCst)];
+            format!(
+                "HTTP/1.1 {}\r\nRetry-After: 0\r\nContent-Length: 0\r\n\r\n",
+                status
+            )
+        })
+    }
+
+    fn send_to(url: String) -> Option<Resource> {
+        let policy = Retry {
+            max_retries: 3,
+            ..Retry::default()
+        };
+        let location = Location {
+            url,
+            ..Location::default()
+        };
+        let agent = build_agent(None, None);
+        send(&policy, &AtomicBool::new(false), || {
+            send_request(&agent, &location, None)
+        })
+    }
+
+    #[test]
+    fn too_many_requests_is_retried_until_it_succeeds() {
+        let (url, requests) = server(&["429 Too Many Requests", "200 OK"]);
+        let resource = send_to(url).unwrap();
+        assert_eq!((resource.status, resource.retries), (200, 1));
+        assert_eq!(requests.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn other_errors_are_not_retried() {
+        let (url, requests) = server(&["404 Not Found", "200 OK"]);
+        let resource = send_to(url).unwrap();
+        assert_eq!((resource.status, resource.retries), (404, 0));
+        assert_eq!(requests.try_iter().count(), 1);
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::assertions::{self, Outcome};
+use super::cookies::Jar;
+use super::retry;
+use super::{send_request, Location};
+
+pub struct RunResult {
+    pub name: String,
+    /// `None` if the request failed without a response.
+    pub status: Option<usize>,
+    pub elapsed_ms: u128,
+    /// Results of the request's assertions; empty if it has none or got no
+    /// response.
+    pub assertions: Vec<Outcome>,
+}
+
+impl RunResult {
+    pub fn passed(&self) -> bool {
+        self.assertions
+            .iter()
+            .all(|outcome| outcome.failure.is_none())
+    }
+}
+
+/// Progress of running every request of a directory, shared with the worker thread.
+#[derive(Default)]
+pub struct FolderRun {
+    pub total: usize,
+    pub results: Vec<RunResult>,
+}
+
+impl FolderRun {
+    pub fn is_done(&self) -> bool {
+        self.results.len() >= self.total
+    }
+
+    /// How many requests were sent, and of those with assertions how many
+    /// passed every one of them.
+    pub fn summary(&self) -> String {
+        let errors = self.results.iter().filter(|r| r.status.is_none()).count();
+        let tested: Vec<&RunResult> = self
+            .results
+            .iter()
+            .filter(|r| !r.assertions.is_empty())
+            .collect();
+        let mut summary = format!("{} of {} requests sent", self.results.len(), self.total);
+        if errors > 0 {
+            summary.push_str(&format!(", {} without response", errors));
+        }
+        if !tested.is_empty() {
+            let passed = tested.iter().filter(|r| r.passed()).count();
+            summary.push_str(&format!(
+                ", {} passed, {} failed",
+                passed,
+                tested.len() - passed
+            ));
+        }
+        summary
+    }
+}
+
+/// Send `locations` one after the other, waiting each request's `delay_ms` first.
+/// Each request comes with the variables visible to it and the agent to send
+/// it with; all of them use the cookie jar `jar`.
+pub fn start(
+    locations: Vec<(Location, BTreeMap<String, String>, ureq::Agent)>,
+    jar: Arc<Mutex<Jar>>,
+    ctx: &egui::Context,
+) -> Arc<Mutex<FolderRun>> {
+    let run = Arc::new(Mutex::new(FolderRun {
+        total: locations.len(),
+        results: Vec::with_capacity(locations.len()),
+    }));
+    let ctx = ctx.clone();
+    let shared = run.clone();
+    thread::spawn(move || {
+        for (location, variables, agent) in locations {
+            if location.delay_ms > 0 {
+                thread::sleep(Duration::from_millis(location.delay_ms));
+            }
+            let resolved = location.resolve(&variables);
+            let start = Instant::now();
+            let resource = retry::send(&resolved.retry, &AtomicBool::new(false), || {
+                send_request(&agent, &resolved, Some(&jar))
+            });
+            let elapsed_ms = start.elapsed().as_millis();
+            shared.lock().unwrap().results.push(RunResult {
+                name: location.name,
+                status: resource.as_ref().map(|r| r.status),
+                elapsed_ms,
+                assertions: resource
+                    .map(|r| assertions::run(&resolved.assertions, &r))
+                    .unwrap_or_default(),
+            });
+            ctx.request_repaint();
+        }
+    });
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::assertions::Assertion;
+    use super::super::{build_agent, test_server};
+    use super::*;
+
+    /// Answers `/ok` with 200 and anything else with 404 after 100 ms.
+    fn server() -> String {
+        let (base, _) = test_server::serve(|request| {
+            let status = match request.path() {
+                "/ok" => "200 OK",
+                _ => {
+                    thread::sleep(Duration::from_millis(100));
+                    "404 Not Found"
+                }
+            };
+            format!("HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status)
+        });
+        base
+    }
+
+    #[test]
+    fn runs_every_request_and_sums_up_the_assertions() {
+        let base = server();
+        let expect_200 = vec![Assertion {
+            value: "200".to_owned(),
+            ..Assertion::default()
+        }];
+        let locations = ["ok", "missing"]
+            .into_iter()
+            .map(|name| {
+                let location = Location {
+                    name: name.to_owned(),
+                    url: format!("{}/{}", base, name),
+                    assertions: expect_200.clone(),
+                    ..Location::default()
+                };
+                (location, BTreeMap::new(), build_agent(None, None))
+            })
+            .collect();
+        let run = start(locations, Default::default(), &egui::Context::default());
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !run.lock().unwrap().is_done() {
+            assert!(Instant::now() < deadline, "the run didn't finish");
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let run = run.lock().unwrap();
+        assert_eq!(run.summary(), "2 of 2 requests sent, 1 passed, 1 failed");
+        let [ok, missing] = &run.results[..] else {
+            panic!("expected two results");
+        };
+        assert_eq!((ok.name.as_str(), ok.status), ("ok", Some(200)));
+        assert!(ok.passed());
+        assert_eq!(
+            (missing.name.as_str(), missing.status),
+            ("missing", Some(404))
+        );
+        assert!(!missing.passed());
+        assert_eq!(
+            missing.assertions[0].failure.as_deref(),
+            Some("status was 404")
+        );
+        assert!(missing.elapsed_ms >= 100, "{}", missing.elapsed_ms);
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+
+use eframe::egui;
+use egui::{Key, KeyboardShortcut, Modifiers};
+
+/// Actions that can be bound to a key.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+pub enum Action {
+    Send,
+    NextTab,
+    PreviousTab,
+    NewRequest,
+    Undo,
+    Redo,
+}
+
+impl Action {
+    pub const ALL: [Action; 6] = [
+        Action::Send,
+        Action::NextTab,
+        Action::PreviousTab,
+        Action::NewRequest,
+        Action::Undo,
+        Action::Redo,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Send => "Send request",
+            Action::NextTab => "Next tab",
+            Action::PreviousTab => "Previous tab",
+            Action::NewRequest => "New scratch request",
+            Action::Undo => "Undo collection change",
+            Action::Redo => "Redo collection change",
+        }
+    }
+
+    /// Whether the action also fires while typing, `in_request` telling if
+    /// the text field is in a request tab; otherwise the key press is left
+    /// to the text field, e.g. for its own undo or a new line.
+    fn works_in_text_fields(&self, in_request: bool) -> bool {
+        match self {
+            Action::Send => in_request,
+            Action::Undo | Action::Redo => false,
+            _ => true,
+        }
+    }
+
+    fn default_shortcut(&self) -> Shortcut {
+        let (modifiers, key) = match self {
+            Action::Send => (Modifiers::COMMAND, Key::Enter),
+            Action::NextTab => (Modifiers::COMMAND, Key::PageDown),
+            Action::PreviousTab => (Modifiers::COMMAND, Key::PageUp),
+            Action::NewRequest => (Modifiers::COMMAND, Key::N),
+            Action::Undo => (Modifiers::COMMAND, Key::Z),
+            Action::Redo => (Modifiers::COMMAND, Key::Y),
+        };
+        Shortcut { modifiers, key }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Shortcut {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl Shortcut {
+    /// A shortcut from a key press, with Ctrl and Cmd both stored as "command"
+    /// so a binding made on one platform works on the others.
+    pub fn from_press(modifiers: Modifiers, key: Key) -> Self {
+        let command = modifiers.ctrl || modifiers.mac_cmd || modifiers.command;
+        Self {
+            modifiers: Modifiers {
+                alt: modifiers.alt,
+                shift: modifiers.shift,
+                command,
+                ..Default::default()
+            },
+            key,
+        }
+    }
+
+    fn to_egui(self) -> KeyboardShortcut {
+        KeyboardShortcut::new(self.modifiers, self.key)
+    }
+
+    pub fn format(&self, ctx: &egui::Context) -> String {
+        ctx.format_shortcut(&self.to_egui())
+    }
+}
+
+/// Key bindings, stored in the settings. Actions missing from the map use
+/// their default shortcut.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Keymap {
+    bindings: BTreeMap<Action, Shortcut>,
+}
+
+impl Keymap {
+    pub fn get(&self, action: Action) -> Shortcut {
+        self.bindings
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_shortcut())
+    }
+
+    pub fn set(&mut self, action: Action, shortcut: Shortcut) {
+        self.bindings.insert(action, shortcut);
+    }
+
+    pub fn reset(&mut self) {
+        self.bindings.clear();
+    }
+
+    /// Actions whose shortcut was pressed this frame; the key presses are
+    /// consumed. `in_request` tells whether the focused text field, if any,
+    /// is in a request tab.
+    pub fn triggered(&self, ctx: &egui::Context, in_request: bool) -> Vec<Action> {
+        let typing = ctx.wants_keyboard_input();
+        let mut input = ctx.input_mut();
+        Action::ALL
+            .into_iter()
+            .filter(|action| !typing || action.works_in_text_fields(in_request))
+            .filter(|action| input.consume_shortcut(&self.get(*action).to_egui()))
+            .collect()
+    }
+}
+
+/// The first key pressed this frame, for recording a new binding.
+pub fn pressed(ctx: &egui::Context) -> Option<Shortcut> {
+    ctx.input().events.iter().find_map(|event| match event {
+        egui::Event::Key {
+            key,
+            pressed: true,
+            modifiers,
+        } => Some(Shortcut::from_press(*modifiers, *key)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn send_fires_only_in_request_text_fields() {
+        assert!(Action::Send.works_in_text_fields(true));
+        assert!(!Action::Send.works_in_text_fields(false));
+        assert!(!Action::Undo.works_in_text_fields(true));
+        assert!(Action::NextTab.works_in_text_fields(false));
+    }
+
+    #[test]
+    fn ctrl_and_cmd_are_stored_as_command() {
+        let ctrl = Shortcut::from_press(Modifiers::CTRL, Key::Enter);
+        let cmd = Shortcut::from_press(Modifiers::MAC_CMD, Key::Enter);
+        assert_eq!(ctrl, cmd);
+        assert_eq!(ctrl, Action::Send.default_shortcut());
+    }
+}
@@ -0,0 +1,124 @@
+use super::{curl, ContentType, Location, Method};
+use crate::variables;
+
+/// What a pasted text was recognized as.
+pub enum Pasted {
+    Curl(Box<Location>),
+    Url(String),
+    Json(String),
+}
+
+impl Pasted {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Pasted::Curl(_) => "curl command: replaces the whole request",
+            Pasted::Url(_) => "URL: a GET request",
+            Pasted::Json(_) => "JSON: the request body",
+        }
+    }
+
+    /// Update `location` with what was pasted, keeping its id and name.
+    pub fn apply(self, location: &mut Location) {
+        match self {
+            Pasted::Curl(parsed) => {
+                *location = Location {
+                    id: location.id.clone(),
+                    name: location.name.clone(),
+                    ..*parsed
+                };
+            }
+            Pasted::Url(url) => {
+                location.url = url;
+                location.method = Method::Get;
+            }
+            Pasted::Json(body) => {
+                location.body = body;
+                location.content_type = ContentType::Json;
+                if !location.method.sends_body() {
+                    location.method = Method::Post;
+                }
+            }
+        }
+    }
+}
+
+pub fn detect(text: &str) -> Option<Pasted> {
+    let text = text.trim();
+    if text.starts_with("curl ") {
+        return curl::parse(text).map(|location| Pasted::Curl(Box::new(location)));
+    }
+    if (text.starts_with("http://") || text.starts_with("https://"))
+        && !text.contains(char::is_whitespace)
+    {
+        return Some(Pasted::Url(text.to_owned()));
+    }
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(value) if value.is_object() || value.is_array() => Some(Pasted::Json(
+            serde_json::to_string_pretty(&value).unwrap_or_else(|_| text.to_owned()),
+        )),
+        _ => None,
+    }
+}
+
+/// The content type `body` looks like: JSON if it parses as an object or
+/// array once `{{variables}}` are filled in, form-urlencoded for a single
+/// line of `key=value` pairs joined by `&`, else raw text. `None` when empty.
+pub fn content_type(body: &str) -> Option<ContentType> {
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+    let filled = variables::resolve(body, |_| Some("0".to_owned()));
+    match serde_json::from_str::<serde_json::Value>(&filled) {
+        Ok(value) if value.is_object() || value.is_array() => return Some(ContentType::Json),
+        _ => {}
+    }
+    let form = !body.contains(char::is_whitespace)
+        && body
+            .split('&')
+            .all(|pair| matches!(pair.split_once('='), Some((key, _)) if !key.is_empty()));
+    Some(match form {
+        true => ContentType::FormUrlEncoded,
+        false => ContentType::Raw,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_json_bodies() {
+        assert_eq!(content_type(r#" {"a": [1, 2]} "#), Some(ContentType::Json));
+        assert_eq!(content_type("[]"), Some(ContentType::Json));
+        // Unquoted variables are filled in before parsing.
+        assert_eq!(
+            content_type(r#"{"id": {{id}}, "name": "{{name}}"}"#),
+            Some(ContentType::Json)
+        );
+        // Scalars are JSON too, but not worth switching for.
+        assert_eq!(content_type("42"), Some(ContentType::Raw));
+    }
+
+    #[test]
+    fn detects_urlencoded_bodies() {
+        assert_eq!(
+            content_type("name=ann&age=7&empty="),
+            Some(ContentType::FormUrlEncoded)
+        );
+        assert_eq!(
+            content_type("q={{query}}"),
+            Some(ContentType::FormUrlEncoded)
+        );
+        assert_eq!(content_type("=value"), Some(ContentType::Raw));
+        assert_eq!(content_type("a=1&b"), Some(ContentType::Raw));
+    }
+
+    #[test]
+    fn falls_back_to_plain_text() {
+        assert_eq!(content_type("hello world"), Some(ContentType::Raw));
+        assert_eq!(content_type("a = 1"), Some(ContentType::Raw));
+        assert_eq!(content_type("{broken"), Some(ContentType::Raw));
+        assert_eq!(content_type(" \n"), None);
+    }
+}
@@ -0,0 +1,127 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+/// A request as the test server read it.
+pub struct Request {
+    /// Index of the connection it came on, counting from 0.
+    pub connection: usize,
+    /// Request line and headers, up to and including the blank line.
+    pub head: String,
+    /// As many bytes as the `Content-Length` header says.
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn path(&self) -> &str {
+        self.head.split(' ').nth(1).unwrap_or_default()
+    }
+
+    /// Value of the header `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        header(&self.head, name)
+    }
+}
+
+/// Serve plain HTTP on a local port, answering every request with the raw
+/// response `respond` gives for it. Returns the base URL, like
+/// `http://127.0.0.1:1234`, and the requests as they arrive.
+pub fn serve<F, R>(respond: F) -> (String, mpsc::Receiver<Request>)
+where
+    F: Fn(&Request) -> R + Send + Sync + 'static,
+    R: Into<Vec<u8>>,
+{
+    let (address, requests) = serve_with(Ok, respond);
+    (format!("http://{}", address), requests)
+}
+
+/// `serve`, speaking over what `wrap` makes of each accepted connection.
+///
+/// A connection is kept open for more requests while the responses have a
+/// `Content-Length` and no `Connection: close`; otherwise the end of the
+/// connection ends the response.
+pub fn serve_with<W, S, F, R>(wrap: W, respond: F) -> (SocketAddr, mpsc::Receiver<Request>)
+where
+    W: Fn(TcpStream) -> std::io::Result<S> + Send + 'static,
+    S: Read + Write + Send + 'static,
+    F: Fn(&Request) -> R + Send + Sync + 'static,
+    R: Into<Vec<u8>>,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let respond = Arc::new(respond);
+    thread::spawn(move || {
+        for (connection, stream) in listener.incoming().enumerate() {
+            let Ok(stream) = stream.and_then(&wrap) else {
+                continue;
+            };
+            let sender = sender.clone();
+            let respond = respond.clone();
+            thread::spawn(move || {
+                let mut stream = BufReader::new(stream);
+                while let Some(head) = read_head(&mut stream) {
+                    let length = header(&head, "Content-Length").map_or(0, |length| {
+                        length.parse().expect("a numeric Content-Length")
+                    });
+                    let mut body = vec![0; length];
+                    if stream.read_exact(&mut body).is_err() {
+                        return;
+                    }
+                    let request = Request {
+                        connection,
+                        head,
+                        body,
+                    };
+                    let response = respond(&request).into();
+                    // The test may be done with the requests.
+                    let _ = sender.send(request);
+                    let stream = stream.get_mut();
+                    if stream
+                        .write_all(&response)
+                        .and_then(|_| stream.flush())
+                        .is_err()
+                        || !keeps_alive(&response)
+                    {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    (address, receiver)
+}
+
+/// Request line and headers, or `None` once the connection has ended.
+fn read_head(stream: &mut impl BufRead) -> Option<String> {
+    let mut head = String::new();
+    loop {
+        match stream.read_line(&mut head) {
+            Ok(0) | Err(_) => return None,
+            // Blank lines before a request are skipped, as HTTP allows.
+            Ok(_) if head == "\r\n" => head.clear(),
+            Ok(_) if head.ends_with("\r\n\r\n") => return Some(head),
+            Ok(_) => {}
+        }
+    }
+}
+
+/// Whether the client can tell where `response` ends without the
+/// connection being closed.
+fn keeps_alive(response: &[u8]) -> bool {
+    let response = String::from_utf8_lossy(response);
+    let head = response.split("\r\n\r\n").next().unwrap_or_default();
+    header(head, "Content-Length").is_some_and(|length| length.parse::<usize>().is_ok())
+        && !header(head, "Connection").is_some_and(|value| value.eq_ignore_ascii_case("close"))
+}
+
+/// Value of the header `name` in the message head `head`, ignoring case.
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then_some(value.trim())
+    })
+}
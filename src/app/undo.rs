@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use super::{Directory, HttpApp, Location};
+
+/// Snapshots kept for undo; older ones are dropped.
+const MAX_STEPS: usize = 50;
+
+/// The collections as they were before an operation.
+#[derive(Clone)]
+struct Snapshot {
+    directory: BTreeMap<String, Directory>,
+    buffers: BTreeMap<String, Location>,
+}
+
+impl Snapshot {
+    fn take(app: &HttpApp) -> Self {
+        Self {
+            directory: app.directory.clone(),
+            buffers: app.context.api_collection.buffers.clone(),
+        }
+    }
+
+    /// Put the snapshot back, closing tabs of requests it doesn't have.
+    fn restore(self, app: &mut HttpApp) {
+        app.directory = self.directory;
+        app.context.api_collection.buffers = self.buffers;
+        app.close_missing_tabs();
+    }
+}
+
+/// Undo and redo of operations that change the collections: deleting
+/// folders and requests, imports and folder edits.
+#[derive(Default)]
+pub struct UndoStack {
+    undo: Vec<(String, Snapshot)>,
+    redo: Vec<(String, Snapshot)>,
+}
+
+impl UndoStack {
+    /// Remember the collections before `operation` changes them.
+    pub fn checkpoint(app: &mut HttpApp, operation: &str) {
+        let snapshot = Snapshot::take(app);
+        app.undo.push(operation, snapshot);
+    }
+
+    /// Let `change` do `operation`, remembering the collections as they were
+    /// only if it changed them.
+    pub fn checkpoint_if_changed<T>(
+        app: &mut HttpApp,
+        operation: &str,
+        change: impl FnOnce(&mut HttpApp) -> T,
+    ) -> T {
+        let snapshot = Snapshot::take(app);
+        let result = change(app);
+        let changed = snapshot.directory != app.directory
+            || snapshot.buffers != app.context.api_collection.buffers;
+        if changed {
+            app.undo.push(operation, snapshot);
+        }
+        result
+    }
+
+    fn push(&mut self, operation: &str, snapshot: Snapshot) {
+        self.undo.push((operation.to_owned(), snapshot));
+        if self.undo.len() > MAX_STEPS {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.last().map(|(operation, _)| operation.as_str())
+    }
+
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|(operation, _)| operation.as_str())
+    }
+
+    pub fn undo(app: &mut HttpApp) {
+        if let Some((operation, snapshot)) = app.undo.undo.pop() {
+            log::info!("undo {}", operation);
+            app.undo.redo.push((operation, Snapshot::take(app)));
+            snapshot.restore(app);
+        }
+    }
+
+    pub fn redo(app: &mut HttpApp) {
+        if let Some((operation, snapshot)) = app.undo.redo.pop() {
+            log::info!("redo {}", operation);
+            app.undo.undo.push((operation, Snapshot::take(app)));
+            snapshot.restore(app);
+        }
+    }
+}
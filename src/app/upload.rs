@@ -0,0 +1,93 @@
+use std::io::Read;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use ureq::{OrAnyStatus, Request, Response};
+
+/// Bodies at least this large show a progress bar while they're sent.
+pub const PROGRESS_THRESHOLD: u64 = 256 * 1024;
+
+/// How much of a request body has been handed to the connection, shared
+/// between the worker thread and the UI.
+#[derive(Default)]
+pub struct Progress {
+    total: AtomicU64,
+    sent: AtomicU64,
+}
+
+impl Progress {
+    pub fn total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    pub fn sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    /// Whether a large body is still being written.
+    pub fn in_flight(&self) -> bool {
+        let total = self.total();
+        total >= PROGRESS_THRESHOLD && self.sent() < total
+    }
+}
+
+/// Reads the body, counting the bytes ureq pulls out to write them.
+struct CountingReader<'a> {
+    body: &'a [u8],
+    progress: &'a Progress,
+    ctx: &'a egui::Context,
+}
+
+impl Read for CountingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.body.read(buf)?;
+        self.progress.sent.fetch_add(n as u64, Ordering::Relaxed);
+        self.ctx.request_repaint();
+        Ok(n)
+    }
+}
+
+/// Send `body` with a `Content-Length`, reporting what has been written to
+/// `progress` if given.
+#[allow(clippy::result_large_err)] // ureq's own error type
+pub fn send(
+    request: Request,
+    content_type: &str,
+    body: &[u8],
+    progress: Option<(&Progress, &egui::Context)>,
+) -> super::Result<Response> {
+    let request = request
+        .set("Content-Type", content_type)
+        .set("Content-Length", &body.len().to_string());
+    match progress {
+        Some((progress, ctx)) => {
+            progress.sent.store(0, Ordering::Relaxed);
+            progress.total.store(body.len() as u64, Ordering::Relaxed);
+            request
+                .send(CountingReader {
+                    body,
+                    progress,
+                    ctx,
+                })
+                .or_any_status()
+        }
+        None => request.send_bytes(body).or_any_status(),
+    }
+}
+
+/// A progress bar for `progress`, if a large body is being sent.
+pub fn ui_progress(ui: &mut egui::Ui, progress: &Arc<Progress>) {
+    if !progress.in_flight() {
+        return;
+    }
+    let (sent, total) = (progress.sent(), progress.total());
+    ui.add(
+        egui::ProgressBar::new(sent as f32 / total as f32)
+            .text(format!(
+                "uploading {:.1} / {:.1} MB",
+                sent as f64 / 1e6,
+                total as f64 / 1e6
+            ))
+            .animate(true),
+    );
+}
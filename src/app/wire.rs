@@ -0,0 +1,259 @@
+use std::cell::RefCell;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use ureq::{ReadWrite, Request, Response, TlsConnector};
+
+use super::{ApiKeyPlacement, Auth, Location};
+
+/// What ureq sends as `User-Agent` unless the request sets one.
+const UREQ_USER_AGENT: &str = "ureq/2.5.0";
+
+/// ureq keeps header names in lower case; show them as the request spelled
+/// them, which is how they go on the wire.
+fn spelled<'a>(name: &'a str, location: &'a Location) -> &'a str {
+    let api_key = match &location.auth {
+        Auth::ApiKey {
+            key,
+            placement: ApiKeyPlacement::Header,
+            ..
+        } => Some(key.as_str()),
+        _ => None,
+    };
+    location
+        .header
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .chain(api_key)
+        .chain([
+            "Cookie",
+            "Authorization",
+            "Content-Type",
+            "Content-Length",
+            "Accept-Encoding",
+        ])
+        .find(|known| known.eq_ignore_ascii_case(name))
+        .unwrap_or(name)
+}
+
+/// The request line and headers of `request` as ureq writes them, followed
+/// by `body` sent with its content type.
+pub fn request(request: &Request, location: &Location, body: Option<(&str, &[u8])>) -> String {
+    let Ok(url) = request.request_url() else {
+        return String::new();
+    };
+    let url = url.as_url();
+    let mut target = url.path().to_owned();
+    if let Some(query) = url.query() {
+        target.push('?');
+        target.push_str(query);
+    }
+    let mut lines = vec![format!("{} {} HTTP/1.1", request.method(), target)];
+    let names = request.header_names();
+    let has = |name: &str| names.iter().any(|n| n.eq_ignore_ascii_case(name));
+    if !has("host") {
+        let host = url.host_str().unwrap_or_default();
+        match url.port() {
+            Some(port) => lines.push(format!("Host: {}:{}", host, port)),
+            None => lines.push(format!("Host: {}", host)),
+        }
+    }
+    if !has("user-agent") {
+        lines.push(format!("User-Agent: {}", UREQ_USER_AGENT));
+    }
+    if !has("accept") {
+        lines.push("Accept: */*".to_owned());
+    }
+    for name in &names {
+        // The body's own headers are set when it is sent.
+        if body.is_some() && matches!(name.as_str(), "content-type" | "content-length") {
+            continue;
+        }
+        for value in request.all(name) {
+            lines.push(format!("{}: {}", spelled(name, location), value));
+        }
+    }
+    let mut text = lines.join("\r\n");
+    text.push_str("\r\n");
+    if let Some((content_type, body)) = body {
+        text.push_str(&format!(
+            "Content-Type: {}\r\nContent-Length: {}\r\n\r\n",
+            content_type,
+            body.len()
+        ));
+        text.push_str(&body_text(body));
+    } else {
+        text.push_str("\r\n");
+    }
+    text
+}
+
+thread_local! {
+    /// What this thread read from TLS connections since `start_receiving`,
+    /// up to the end of the first response head.
+    static RECEIVED: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// ureq's rustls connector, also keeping what each connection reads in
+/// `RECEIVED`. ureq lowercases response header names; a TLS connector is
+/// the one place it lets the bytes as the server sent them be seen.
+pub struct Recording(pub Arc<rustls::ClientConfig>);
+
+impl TlsConnector for Recording {
+    fn connect(
+        &self,
+        dns_name: &str,
+        io: Box<dyn ReadWrite>,
+    ) -> Result<Box<dyn ReadWrite>, ureq::Error> {
+        let stream = TlsConnector::connect(&self.0, dns_name, io)?;
+        Ok(Box::new(Recorded(stream)))
+    }
+}
+
+#[derive(Debug)]
+struct Recorded(Box<dyn ReadWrite>);
+
+impl Read for Recorded {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.0.read(buf)?;
+        RECEIVED.with(|received| {
+            let mut received = received.borrow_mut();
+            if head_end(&received).is_none() {
+                received.extend_from_slice(&buf[..n]);
+            }
+        });
+        Ok(n)
+    }
+}
+
+impl Write for Recorded {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl ReadWrite for Recorded {
+    fn socket(&self) -> Option<&TcpStream> {
+        self.0.socket()
+    }
+}
+
+fn head_end(bytes: &[u8]) -> Option<usize> {
+    bytes
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .map(|i| i + 4)
+}
+
+/// Forget what was received, before sending a request.
+pub fn start_receiving() {
+    RECEIVED.with(|received| received.borrow_mut().clear());
+}
+
+/// The status line and headers read since `start_receiving`, byte for
+/// byte. `None` over plain HTTP, which ureq reads without a connector.
+pub fn received_head() -> Option<String> {
+    RECEIVED.with(|received| {
+        let received = received.borrow();
+        let end = head_end(&received)?;
+        Some(String::from_utf8_lossy(&received[..end]).into_owned())
+    })
+}
+
+/// How each line of the header `name` in the response `head` spells it,
+/// in order.
+pub fn spellings<'a>(head: &'a str, name: &str) -> Vec<&'a str> {
+    head.lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, _)| key.trim())
+        .filter(|key| key.eq_ignore_ascii_case(name))
+        .collect()
+}
+
+/// Status line and headers of `response` as ureq parsed them, for when
+/// `received_head` has nothing; ureq only has the header names in lower case.
+pub fn response_head(response: &Response) -> String {
+    let mut text = format!(
+        "{} {} {}\r\n",
+        response.http_version(),
+        response.status(),
+        response.status_text()
+    );
+    let mut seen: Vec<String> = Vec::new();
+    for name in response.headers_names() {
+        // Names repeat once per header line, `all` has every value already.
+        if seen.contains(&name) {
+            continue;
+        }
+        seen.push(name.clone());
+        for value in response.all(&name) {
+            text.push_str(&format!("{}: {}\r\n", name, value));
+        }
+    }
+    text.push_str("\r\n");
+    text
+}
+
+/// `body` as text, or a note on its size if it isn't UTF-8.
+pub fn body_text(body: &[u8]) -> String {
+    match std::str::from_utf8(body) {
+        Ok(text) => text.to_owned(),
+        Err(_) => format!("[{} bytes of binary data]", body.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_the_request_line_and_headers() {
+        let location = Location {
+            header: vec![("X-Trace-Id".to_owned(), "7".to_owned())],
+            ..Location::default()
+        };
+        let request = ureq::agent()
+            .post("http://example.com:8080/items?page=2")
+            .set("X-Trace-Id", "7")
+            .set("Content-Type", "text/plain");
+        assert_eq!(
+            super::request(&request, &location, Some(("application/json", b"{}"))),
+            "POST /items?page=2 HTTP/1.1\r\n\
+             Host: example.com:8080\r\n\
+             User-Agent: ureq/2.5.0\r\n\
+             Accept: */*\r\n\
+             X-Trace-Id: 7\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 2\r\n\
+             \r\n\
+             {}"
+        );
+        let request = ureq::agent().get("https://example.com/");
+        assert_eq!(
+            super::request(&request, &Location::default(), None),
+            "GET / HTTP/1.1\r\nHost: example.com\r\nUser-Agent: ureq/2.5.0\r\nAccept: */*\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn spellings_follow_the_head() {
+        let head = "HTTP/1.1 200 OK\r\nSet-Cookie: a=1\r\nETag: \"x\"\r\nset-cookie: b=2\r\n\r\n";
+        assert_eq!(spellings(head, "set-cookie"), ["Set-Cookie", "set-cookie"]);
+        assert_eq!(spellings(head, "etag"), ["ETag"]);
+        assert!(spellings(head, "content-type").is_empty());
+        // The status line isn't a header, whatever it contains.
+        assert!(spellings("HTTP/1.1 200 OK: fine\r\n\r\n", "http/1.1 200 ok").is_empty());
+    }
+
+    #[test]
+    fn binary_bodies_are_summarized() {
+        assert_eq!(body_text(b"text"), "text");
+        assert_eq!(body_text(&[0xff, 0xfe]), "[2 bytes of binary data]");
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use super::{history::History, Directory, Environment, HttpApp, Location};
+
+/// File extension of workspace archives, which are plain zip files inside.
+pub const EXTENSION: &str = "orient";
+
+/// Everything that belongs to one workspace, for the workspaces that aren't
+/// currently open. The open one lives in `HttpApp` and `MyContext` directly.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Workspace {
+    directory: BTreeMap<String, Directory>,
+    buffers: BTreeMap<String, Location>,
+    environments: BTreeMap<String, Environment>,
+    active_environment: String,
+    global_variables: Vec<(String, String)>,
+    history: History,
+    tree: egui_dock::Tree<String>,
+}
+
+impl Workspace {
+    /// Move the open workspace out of `app`, leaving it empty.
+    pub fn take(app: &mut HttpApp) -> Self {
+        let context = &mut app.context;
+        Self {
+            directory: std::mem::take(&mut app.directory),
+            buffers: std::mem::take(&mut context.api_collection.buffers),
+            environments: std::mem::take(&mut context.environments),
+            active_environment: std::mem::take(&mut context.active_environment),
+            global_variables: std::mem::take(&mut context.global_variables),
+            history: std::mem::take(&mut context.history),
+            tree: std::mem::take(&mut app.tree),
+        }
+    }
+
+    /// Make this the open workspace of `app`, replacing what's there.
+    pub fn restore(self, app: &mut HttpApp) {
+        let context = &mut app.context;
+        app.directory = self.directory;
+        context.api_collection.buffers = self.buffers;
+        context.environments = self.environments;
+        context.active_environment = self.active_environment;
+        context.global_variables = self.global_variables;
+        context.history = self.history;
+        app.tree = self.tree;
+        app.close_missing_tabs();
+    }
+}
+
+/// Delete the open workspace and open another one, if there is one.
+pub fn delete_current(app: &mut HttpApp) {
+    let Some(next) = app.workspaces.keys().next().cloned() else {
+        return;
+    };
+    Workspace::take(app);
+    app.context.cookie_jars.clear();
+    app.workspace = next.clone();
+    app.workspaces
+        .remove(&next)
+        .unwrap_or_default()
+        .restore(app);
+}
+
+/// Close the open workspace and open `name`, creating it if it doesn't exist.
+pub fn switch(app: &mut HttpApp, name: &str) {
+    if app.workspace == name {
+        return;
+    }
+    let current = Workspace::take(app);
+    // Cookies belong to the collections they were collected with.
+    app.context.cookie_jars.clear();
+    let previous = std::mem::replace(&mut app.workspace, name.to_owned());
+    app.workspaces.insert(previous, current);
+    app.workspaces.remove(name).unwrap_or_default().restore(app);
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct Collections {
+    directory: BTreeMap<String, Directory>,
+    buffers: BTreeMap<String, Location>,
+}
+
+/// Write collections, environments, global variables and, if asked, the
+/// request history to a single archive at `path`.
+pub fn export(app: &HttpApp, path: &Path, include_history: bool) -> std::io::Result<()> {
+    let mut zip = zip::ZipWriter::new(File::create(path)?);
+    let options = zip::write::FileOptions::default();
+
+    let collections = Collections {
+        directory: app.directory.clone(),
+        buffers: app.context.api_collection.buffers.clone(),
+    };
+    zip.start_file("collections.json", options)?;
+    serde_json::to_writer_pretty(&mut zip, &collections)?;
+    zip.start_file("environments.json", options)?;
+    serde_json::to_writer_pretty(&mut zip, &app.context.environments)?;
+    zip.start_file("globals.json", options)?;
+    serde_json::to_writer_pretty(&mut zip, &app.context.global_variables)?;
+    if include_history {
+        zip.start_file("history.json", options)?;
+        serde_json::to_writer_pretty(&mut zip, &app.context.history)?;
+    }
+    zip.finish()?.flush()
+}
+
+fn read_entry<T: serde::de::DeserializeOwned>(
+    archive: &mut zip::ZipArchive<File>,
+    name: &str,
+) -> std::io::Result<Option<T>> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    Ok(Some(serde_json::from_str(&contents)?))
+}
+
+/// Merge an archive written by `export` into the workspace. Entries with the
+/// same id as existing ones replace them. Returns a summary for the user.
+pub fn import(app: &mut HttpApp, path: &Path) -> std::io::Result<String> {
+    let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+
+    let mut summary = Vec::new();
+    if let Some(collections) = read_entry::<Collections>(&mut archive, "collections.json")? {
+        summary.push(format!(
+            "{} folders with {} requests",
+            collections.directory.len(),
+            collections.buffers.len()
+        ));
+        app.directory.extend(collections.directory);
+        app.context
+            .api_collection
+            .buffers
+            .extend(collections.buffers);
+    }
+    if let Some(environments) =
+        read_entry::<BTreeMap<String, Environment>>(&mut archive, "environments.json")?
+    {
+        summary.push(format!("{} environments", environments.len()));
+        app.context.environments.extend(environments);
+    }
+    if let Some(globals) = read_entry::<Vec<(String, String)>>(&mut archive, "globals.json")? {
+        summary.push(format!("{} global variables", globals.len()));
+        let existing = &mut app.context.global_variables;
+        for (name, value) in globals {
+            match existing.iter_mut().find(|(n, _)| *n == name) {
+                Some(variable) => variable.1 = value,
+                None => existing.push((name, value)),
+            }
+        }
+    }
+    if let Some(history) = read_entry::<History>(&mut archive, "history.json")? {
+        summary.push(format!("{} history entries", history.entries.len()));
+        let entries = &mut app.context.history.entries;
+        entries.extend(history.entries);
+        entries.sort_by_key(|e| e.timestamp);
+        entries.dedup();
+        app.context.history.truncate();
+    }
+    Ok(format!("Imported {}", summary.join(", ")))
+}
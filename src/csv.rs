@@ -0,0 +1,48 @@
+/// Field delimiter for CSV-like content types, or `None` for anything else.
+pub fn delimiter(content_type: &str) -> Option<char> {
+    match content_type {
+        "text/csv" | "application/csv" => Some(','),
+        "text/tab-separated-values" => Some('\t'),
+        _ => None,
+    }
+}
+
+/// Split CSV `text` into rows of fields (RFC 4180): fields may be quoted,
+/// quoted fields may contain delimiters and line breaks, and `""` inside
+/// quotes is a literal quote.
+pub fn parse(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                c => field.push(c),
+            }
+        } else if c == delimiter {
+            row.push(std::mem::take(&mut field));
+        } else {
+            match c {
+                '"' if field.is_empty() => quoted = true,
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' | '\r' => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                c => field.push(c),
+            }
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
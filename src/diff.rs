@@ -0,0 +1,158 @@
+/// One line of a line-based diff.
+#[derive(Debug, PartialEq)]
+pub enum Line<'a> {
+    Same(&'a str),
+    Added(&'a str),
+    Removed(&'a str),
+    /// A removed line directly replaced by an added one.
+    Changed(&'a str, &'a str),
+}
+
+/// Above this many line pairs between the common start and end, the middle
+/// is shown as removed then added instead of being aligned.
+const MAX_CELLS: usize = 4_000_000;
+
+/// The lines of `old` and `new` aligned on a longest common subsequence,
+/// with each run of removed lines followed by added ones paired up as
+/// changed lines.
+pub fn lines<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (a, b) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    let mut out: Vec<Line> = old[..prefix].iter().map(|l| Line::Same(l)).collect();
+    let mut middle = Vec::new();
+    if a.len().saturating_mul(b.len()) > MAX_CELLS {
+        middle.extend(a.iter().map(|l| Line::Removed(l)));
+        middle.extend(b.iter().map(|l| Line::Added(l)));
+    } else {
+        // lcs[i][j]: length of the common subsequence of a[i..] and b[j..].
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                middle.push(Line::Same(a[i]));
+                i += 1;
+                j += 1;
+            } else if j == b.len()
+                || (i < a.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+            {
+                middle.push(Line::Removed(a[i]));
+                i += 1;
+            } else {
+                middle.push(Line::Added(b[j]));
+                j += 1;
+            }
+        }
+    }
+    out.extend(pair_changes(middle));
+    out.extend(old[old.len() - suffix..].iter().map(|l| Line::Same(l)));
+    out
+}
+
+/// Pair each run of removed lines with the added lines right after it.
+fn pair_changes<'a>(lines: Vec<Line<'a>>) -> Vec<Line<'a>> {
+    let mut out = Vec::with_capacity(lines.len());
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let flush = |out: &mut Vec<Line<'a>>, removed: &mut Vec<&'a str>, added: &mut Vec<&'a str>| {
+        let pairs = removed.len().min(added.len());
+        out.extend(
+            removed
+                .iter()
+                .zip(added.iter())
+                .map(|(old, new)| Line::Changed(old, new)),
+        );
+        out.extend(removed[pairs..].iter().map(|l| Line::Removed(l)));
+        out.extend(added[pairs..].iter().map(|l| Line::Added(l)));
+        removed.clear();
+        added.clear();
+    };
+    for line in lines {
+        match line {
+            Line::Removed(l) if added.is_empty() => removed.push(l),
+            Line::Added(l) => added.push(l),
+            line => {
+                flush(&mut out, &mut removed, &mut added);
+                match line {
+                    Line::Removed(l) => removed.push(l),
+                    line => out.push(line),
+                }
+            }
+        }
+    }
+    flush(&mut out, &mut removed, &mut added);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_texts_are_the_same() {
+        assert_eq!(
+            lines("a\nb", "a\nb"),
+            vec![Line::Same("a"), Line::Same("b")]
+        );
+        assert!(lines("", "").is_empty());
+    }
+
+    #[test]
+    fn aligns_added_and_removed_lines() {
+        assert_eq!(
+            lines("a\nb\nc\nd", "a\nc\nd\ne"),
+            vec![
+                Line::Same("a"),
+                Line::Removed("b"),
+                Line::Same("c"),
+                Line::Same("d"),
+                Line::Added("e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pairs_replaced_lines() {
+        assert_eq!(
+            lines(
+                "{\n  \"id\": 1,\n  \"x\": 2\n}",
+                "{\n  \"id\": 2,\n  \"x\": 2\n}"
+            ),
+            vec![
+                Line::Same("{"),
+                Line::Changed("  \"id\": 1,", "  \"id\": 2,"),
+                Line::Same("  \"x\": 2"),
+                Line::Same("}"),
+            ]
+        );
+        assert_eq!(
+            lines("a\nb", "c\nd\ne"),
+            vec![
+                Line::Changed("a", "c"),
+                Line::Changed("b", "d"),
+                Line::Added("e")
+            ]
+        );
+    }
+}
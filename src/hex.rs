@@ -0,0 +1,40 @@
+/// Decode hex digits into bytes, ignoring whitespace so dumps can be pasted as-is.
+pub fn decode(text: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> = text.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    let pairs = digits.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(format!("odd number of hex digits ({})", digits.len()));
+    }
+    pairs
+        .enumerate()
+        .map(|(i, pair)| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| format!("invalid hex byte at position {}", i))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_pasted_dumps() {
+        assert_eq!(decode("de ad\nBE\tef"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(decode(""), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn reports_bad_digits() {
+        assert_eq!(
+            decode("abc"),
+            Err("odd number of hex digits (3)".to_owned())
+        );
+        assert_eq!(
+            decode("00 zz"),
+            Err("invalid hex byte at position 1".to_owned())
+        );
+    }
+}
@@ -0,0 +1,239 @@
+/// Standard reason phrase and a one-line description for an HTTP status code.
+pub fn explain(status: usize) -> Option<(&'static str, &'static str)> {
+    let explanation = match status {
+        100 => (
+            "Continue",
+            "The server got the request headers; send the body.",
+        ),
+        101 => (
+            "Switching Protocols",
+            "The server is switching to the protocol asked for in Upgrade.",
+        ),
+        102 => (
+            "Processing",
+            "The server accepted the request but hasn't finished it yet.",
+        ),
+        103 => (
+            "Early Hints",
+            "Preliminary headers sent ahead of the final response.",
+        ),
+        200 => ("OK", "The request succeeded."),
+        201 => (
+            "Created",
+            "The request succeeded and a new resource was created.",
+        ),
+        202 => (
+            "Accepted",
+            "The request was accepted for processing, which isn't complete yet.",
+        ),
+        203 => (
+            "Non-Authoritative Information",
+            "The returned metadata comes from a copy, not the origin server.",
+        ),
+        204 => (
+            "No Content",
+            "The request succeeded and there is no body to return.",
+        ),
+        205 => (
+            "Reset Content",
+            "The request succeeded; the client should reset its document view.",
+        ),
+        206 => (
+            "Partial Content",
+            "Only the part of the resource asked for with Range is returned.",
+        ),
+        207 => (
+            "Multi-Status",
+            "The body holds separate status codes for several operations (WebDAV).",
+        ),
+        208 => (
+            "Already Reported",
+            "The members were already listed earlier in the response (WebDAV).",
+        ),
+        226 => (
+            "IM Used",
+            "The response is the result of instance manipulations on the resource.",
+        ),
+        300 => (
+            "Multiple Choices",
+            "The resource has several representations to choose from.",
+        ),
+        301 => (
+            "Moved Permanently",
+            "The resource has a new permanent URL, given in Location.",
+        ),
+        302 => (
+            "Found",
+            "The resource is temporarily at the URL given in Location.",
+        ),
+        303 => (
+            "See Other",
+            "Fetch the result with a GET to the URL given in Location.",
+        ),
+        304 => (
+            "Not Modified",
+            "The cached copy is still valid; no body is sent.",
+        ),
+        307 => (
+            "Temporary Redirect",
+            "Repeat the request, same method, at the URL given in Location.",
+        ),
+        308 => (
+            "Permanent Redirect",
+            "Repeat this and future requests, same method, at the new URL.",
+        ),
+        400 => (
+            "Bad Request",
+            "The server can't process the request because it's malformed.",
+        ),
+        401 => (
+            "Unauthorized",
+            "Authentication is required or the credentials were rejected.",
+        ),
+        402 => (
+            "Payment Required",
+            "Reserved; sometimes used when a payment or quota is needed.",
+        ),
+        403 => (
+            "Forbidden",
+            "The server understood the request but refuses to authorize it.",
+        ),
+        404 => ("Not Found", "The server can't find the requested resource."),
+        405 => (
+            "Method Not Allowed",
+            "The resource doesn't support this HTTP method.",
+        ),
+        406 => (
+            "Not Acceptable",
+            "No representation matches the request's Accept headers.",
+        ),
+        407 => (
+            "Proxy Authentication Required",
+            "The proxy needs the client to authenticate.",
+        ),
+        408 => (
+            "Request Timeout",
+            "The server gave up waiting for the request.",
+        ),
+        409 => (
+            "Conflict",
+            "The request conflicts with the current state of the resource.",
+        ),
+        410 => ("Gone", "The resource was removed and won't come back."),
+        411 => (
+            "Length Required",
+            "The server needs a Content-Length header.",
+        ),
+        412 => (
+            "Precondition Failed",
+            "A condition in the request headers (e.g. If-Match) didn't hold.",
+        ),
+        413 => (
+            "Content Too Large",
+            "The request body is larger than the server accepts.",
+        ),
+        414 => (
+            "URI Too Long",
+            "The request URL is longer than the server accepts.",
+        ),
+        415 => (
+            "Unsupported Media Type",
+            "The server doesn't accept the body's Content-Type.",
+        ),
+        416 => (
+            "Range Not Satisfiable",
+            "The requested Range lies outside the resource.",
+        ),
+        417 => (
+            "Expectation Failed",
+            "The server can't meet the Expect header.",
+        ),
+        418 => (
+            "I'm a teapot",
+            "The server refuses to brew coffee because it is a teapot.",
+        ),
+        421 => (
+            "Misdirected Request",
+            "The request went to a server that can't answer for this host.",
+        ),
+        422 => (
+            "Unprocessable Content",
+            "The body is well-formed but fails validation.",
+        ),
+        423 => ("Locked", "The resource is locked (WebDAV)."),
+        424 => (
+            "Failed Dependency",
+            "The request depended on another request that failed (WebDAV).",
+        ),
+        425 => (
+            "Too Early",
+            "The server won't process a request that might be replayed.",
+        ),
+        426 => (
+            "Upgrade Required",
+            "The client must switch to the protocol given in Upgrade.",
+        ),
+        428 => (
+            "Precondition Required",
+            "The server requires the request to be conditional.",
+        ),
+        429 => (
+            "Too Many Requests",
+            "Rate limited; see Retry-After for when to try again.",
+        ),
+        431 => (
+            "Request Header Fields Too Large",
+            "The request headers are too large.",
+        ),
+        451 => (
+            "Unavailable For Legal Reasons",
+            "The resource is blocked for legal reasons.",
+        ),
+        500 => (
+            "Internal Server Error",
+            "The server hit an unexpected condition.",
+        ),
+        501 => (
+            "Not Implemented",
+            "The server doesn't support the functionality required.",
+        ),
+        502 => (
+            "Bad Gateway",
+            "A gateway or proxy got an invalid response upstream.",
+        ),
+        503 => (
+            "Service Unavailable",
+            "The server is overloaded or down for maintenance.",
+        ),
+        504 => (
+            "Gateway Timeout",
+            "A gateway or proxy didn't get a timely response upstream.",
+        ),
+        505 => (
+            "HTTP Version Not Supported",
+            "The server doesn't support the request's HTTP version.",
+        ),
+        506 => (
+            "Variant Also Negotiates",
+            "The server's content negotiation is misconfigured.",
+        ),
+        507 => (
+            "Insufficient Storage",
+            "The server can't store what's needed to complete the request.",
+        ),
+        508 => (
+            "Loop Detected",
+            "The server found an infinite loop while processing (WebDAV).",
+        ),
+        510 => (
+            "Not Extended",
+            "Further extensions to the request are required.",
+        ),
+        511 => (
+            "Network Authentication Required",
+            "The client must authenticate to get network access.",
+        ),
+        _ => return None,
+    };
+    Some(explanation)
+}
@@ -0,0 +1,91 @@
+use serde_json::Value;
+
+/// Re-indent a JSON document with two spaces per level. Keys keep their
+/// order and numbers their spelling, which parsing into a `Value` would
+/// not. Invalid JSON gives the parse error with its line and column.
+pub fn pretty(text: &str) -> Result<String, String> {
+    serde_json::from_str::<Value>(text).map_err(|err| err.to_string())?;
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut chars = text.trim().chars().peekable();
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+                if matches!(chars.peek(), Some('}' | ']')) {
+                    out.extend(chars.next());
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indents_nested_objects_and_arrays() {
+        assert_eq!(
+            pretty(r#"{"b":1,"a":[1,{"c":null}],"e":{},"f":[ ]}"#).unwrap(),
+            "{\n  \"b\": 1,\n  \"a\": [\n    1,\n    {\n      \"c\": null\n    }\n  ],\n  \"e\": {},\n  \"f\": []\n}"
+        );
+        // Already pretty input comes out the same, numbers spelled as given.
+        let formatted = "[\n  1.50,\n  2e3\n]";
+        assert_eq!(pretty(formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn strings_are_copied_verbatim() {
+        assert_eq!(
+            pretty(r#"{"a":"{[,:]}","b":"say \"hi\", \\ {"}"#).unwrap(),
+            "{\n  \"a\": \"{[,:]}\",\n  \"b\": \"say \\\"hi\\\", \\\\ {\"\n}"
+        );
+    }
+
+    #[test]
+    fn invalid_json_reports_where() {
+        assert_eq!(
+            pretty("{\n  \"a\": 1\n  \"b\": 2\n}").unwrap_err(),
+            "expected `,` or `}` at line 3 column 3"
+        );
+        assert_eq!(
+            pretty("[1,]").unwrap_err(),
+            "trailing comma at line 1 column 4"
+        );
+    }
+}
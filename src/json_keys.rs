@@ -0,0 +1,65 @@
+use std::ops::Range;
+
+use serde_json::Value;
+
+/// Whether the object key `key` matches the filter `needle`: a
+/// case-insensitive substring.
+pub fn matches(key: &str, needle: &str) -> bool {
+    key.to_lowercase().contains(&needle.to_lowercase())
+}
+
+/// `value` cut down to the branches leading to a matching key, which keep
+/// their whole value. `None` if nothing matches.
+pub fn prune(value: &Value, needle: &str) -> Option<Value> {
+    match value {
+        Value::Object(map) => {
+            let kept: serde_json::Map<String, Value> = map
+                .iter()
+                .filter_map(|(key, value)| {
+                    if matches(key, needle) {
+                        Some((key.clone(), value.clone()))
+                    } else {
+                        Some((key.clone(), prune(value, needle)?))
+                    }
+                })
+                .collect();
+            (!kept.is_empty()).then_some(Value::Object(kept))
+        }
+        Value::Array(items) => {
+            let kept: Vec<Value> = items.iter().filter_map(|v| prune(v, needle)).collect();
+            (!kept.is_empty()).then_some(Value::Array(kept))
+        }
+        _ => None,
+    }
+}
+
+/// Byte ranges of the matching keys, quotes included, in pretty-printed
+/// JSON `text`. A key is a string followed by a colon.
+pub fn key_ranges(text: &str, needle: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'"' {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i] != b'"' {
+            i += if bytes[i] == b'\\' { 2 } else { 1 };
+        }
+        let end = (i + 1).min(bytes.len());
+        i = end;
+        let is_key = text[end..].trim_start().starts_with(':');
+        if is_key
+            && matches(
+                &text[start + 1..end.saturating_sub(1).max(start + 1)],
+                needle,
+            )
+        {
+            ranges.push(start..end);
+        }
+    }
+    ranges
+}
@@ -0,0 +1,181 @@
+use serde_json::Value;
+
+/// One step of a path.
+#[derive(Debug, PartialEq)]
+enum Segment {
+    /// `.name` or `['name']`
+    Key(String),
+    /// `[0]`; negative indices count from the end.
+    Index(i64),
+    /// `.*` or `[*]`: every member or element.
+    Wildcard,
+    /// `..name`: `name` at any depth.
+    Descendant(String),
+}
+
+/// Parse a JSONPath-style expression such as `$.data.items[0].id`. The
+/// leading `$` is optional.
+fn parse(path: &str) -> Result<Vec<Segment>, String> {
+    let path = path.trim();
+    let mut rest = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("..") {
+            let (name, after) = split_name(after);
+            if name.is_empty() {
+                return Err("expected a key after `..`".to_owned());
+            }
+            segments.push(Segment::Descendant(name.to_owned()));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let (name, after) = split_name(after);
+            segments.push(match name {
+                "" => return Err("expected a key after `.`".to_owned()),
+                "*" => Segment::Wildcard,
+                name => Segment::Key(name.to_owned()),
+            });
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(|| "missing `]`".to_owned())?;
+            let inside = after[..end].trim();
+            segments.push(match inside {
+                "*" => Segment::Wildcard,
+                _ if inside.len() >= 2
+                    && (inside.starts_with('\'') && inside.ends_with('\'')
+                        || inside.starts_with('"') && inside.ends_with('"')) =>
+                {
+                    Segment::Key(inside[1..inside.len() - 1].to_owned())
+                }
+                _ => {
+                    Segment::Index(inside.parse().map_err(|_| {
+                        format!("`[{}]` is not an index, `*` or a quoted key", inside)
+                    })?)
+                }
+            });
+            rest = &after[end + 1..];
+        } else if segments.is_empty() && rest.len() == path.len() {
+            // `data.items` without the `$.` is taken as a key path too.
+            let (name, after) = split_name(rest);
+            segments.push(Segment::Key(name.to_owned()));
+            rest = after;
+        } else {
+            return Err(format!("unexpected `{}`", rest));
+        }
+    }
+    Ok(segments)
+}
+
+/// A key up to the next `.` or `[`, and what follows.
+fn split_name(text: &str) -> (&str, &str) {
+    let end = text.find(['.', '[']).unwrap_or(text.len());
+    text.split_at(end)
+}
+
+fn descendants<'a>(value: &'a Value, name: &str, out: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                if key == name {
+                    out.push(child);
+                }
+                descendants(child, name, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                descendants(item, name, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The values `path` selects in `value`, in order.
+pub fn query<'a>(value: &'a Value, path: &str) -> Result<Vec<&'a Value>, String> {
+    let mut current = vec![value];
+    for segment in parse(path)? {
+        let mut next = Vec::new();
+        for value in current {
+            match (&segment, value) {
+                (Segment::Key(key), Value::Object(map)) => next.extend(map.get(key)),
+                (Segment::Index(index), Value::Array(items)) => {
+                    let index = match *index {
+                        i if i < 0 => items.len().checked_sub(i.unsigned_abs() as usize),
+                        i => Some(i as usize),
+                    };
+                    next.extend(index.and_then(|i| items.get(i)));
+                }
+                (Segment::Wildcard, Value::Object(map)) => next.extend(map.values()),
+                (Segment::Wildcard, Value::Array(items)) => next.extend(items.iter()),
+                (Segment::Descendant(name), value) => descendants(value, name, &mut next),
+                _ => {}
+            }
+        }
+        current = next;
+    }
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "data": {
+                "items": [
+                    {"id": 1, "tags": ["a"]},
+                    {"id": 2, "tags": []},
+                    {"id": 3, "owner": {"id": 9}},
+                ],
+                "total": 3,
+                "odd key": true,
+            }
+        })
+    }
+
+    #[test]
+    fn selects_keys_and_indices() {
+        let doc = sample();
+        assert_eq!(query(&doc, "$.data.total").unwrap(), vec![&json!(3)]);
+        assert_eq!(query(&doc, "data.total").unwrap(), vec![&json!(3)]);
+        assert_eq!(
+            query(&doc, "$.data['odd key']").unwrap(),
+            vec![&json!(true)]
+        );
+        assert_eq!(query(&doc, "$.data.items[0].id").unwrap(), vec![&json!(1)]);
+        assert_eq!(query(&doc, "$.data.items[-1].id").unwrap(), vec![&json!(3)]);
+        assert!(query(&doc, "$.data.items[5]").unwrap().is_empty());
+        assert!(query(&doc, "$.data.items[-5]").unwrap().is_empty());
+        assert!(query(&doc, "$.missing.id").unwrap().is_empty());
+        assert_eq!(query(&doc, "$").unwrap(), vec![&doc]);
+    }
+
+    #[test]
+    fn selects_wildcards_and_descendants() {
+        let doc = sample();
+        assert_eq!(
+            query(&doc, "$.data.items[*].id").unwrap(),
+            vec![&json!(1), &json!(2), &json!(3)]
+        );
+        assert_eq!(
+            query(&doc, "$.data.items.*.tags[0]").unwrap(),
+            vec![&json!("a")]
+        );
+        assert_eq!(
+            query(&doc, "$..id").unwrap(),
+            vec![&json!(1), &json!(2), &json!(3), &json!(9)]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_paths() {
+        let doc = sample();
+        assert!(query(&doc, "$.data.items[0").is_err());
+        assert!(query(&doc, "$.data.items[x]").is_err());
+        assert!(query(&doc, "$.").is_err());
+        assert!(query(&doc, "$..").is_err());
+    }
+}
@@ -0,0 +1,364 @@
+use serde_json::{Map, Value};
+
+/// Validate `instance` against `schema`, returning one message per violation.
+///
+/// Covers the commonly used keywords of drafts 4 to 2020-12: `type`, `enum`,
+/// `const`, the numeric, string, array and object constraints,
+/// `allOf`/`anyOf`/`oneOf`/`not` and local `$ref`s. `format` and remote
+/// references are ignored.
+pub fn validate(schema: &Value, instance: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    check(schema, schema, instance, "$", 0, &mut errors);
+    errors
+}
+
+/// References followed in a row without moving into the instance, above
+/// which they are taken for a cycle such as `{"$ref": "#"}`.
+const MAX_REFS: usize = 32;
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "number" => value.is_number(),
+        "integer" => match value {
+            Value::Number(n) => n.as_f64().is_some_and(|f| f.fract() == 0.0),
+            _ => false,
+        },
+        other => type_name(value) == other,
+    }
+}
+
+/// Follow a local reference such as `#/definitions/pet` or `#/$defs/pet`.
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    root.pointer(pointer)
+}
+
+/// `refs` counts the references followed to get to `schema` since the last
+/// step into `instance`.
+fn check(
+    root: &Value,
+    schema: &Value,
+    instance: &Value,
+    path: &str,
+    refs: usize,
+    errors: &mut Vec<String>,
+) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(format!("{}: no value is allowed here", path));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match resolve_ref(root, reference) {
+            Some(_) if refs >= MAX_REFS => {
+                errors.push(format!("{}: $ref {} loops back on itself", path, reference))
+            }
+            Some(target) => check(root, target, instance, path, refs + 1, errors),
+            None => errors.push(format!("{}: can't resolve $ref {}", path, reference)),
+        }
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(instance, t)) {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                path,
+                types.join(" or "),
+                type_name(instance)
+            ));
+            return;
+        }
+    }
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(instance) {
+            errors.push(format!(
+                "{}: {} is not one of {}",
+                path,
+                instance,
+                Value::Array(options.clone())
+            ));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != instance {
+            errors.push(format!("{}: expected {}, got {}", path, expected, instance));
+        }
+    }
+
+    for (keyword, subschemas) in ["allOf", "anyOf", "oneOf"]
+        .iter()
+        .filter_map(|k| Some((*k, schema.get(*k)?.as_array()?)))
+    {
+        let passing = subschemas
+            .iter()
+            .filter(|sub| {
+                let mut sub_errors = Vec::new();
+                check(root, sub, instance, path, refs, &mut sub_errors);
+                if keyword == "allOf" {
+                    errors.extend(sub_errors.iter().cloned());
+                }
+                sub_errors.is_empty()
+            })
+            .count();
+        match keyword {
+            "anyOf" if passing == 0 => {
+                errors.push(format!("{}: matches none of the anyOf schemas", path))
+            }
+            "oneOf" if passing != 1 => errors.push(format!(
+                "{}: matches {} of the oneOf schemas instead of exactly one",
+                path, passing
+            )),
+            _ => {}
+        }
+    }
+    if let Some(not) = schema.get("not") {
+        let mut sub_errors = Vec::new();
+        check(root, not, instance, path, refs, &mut sub_errors);
+        if sub_errors.is_empty() {
+            errors.push(format!("{}: must not match the \"not\" schema", path));
+        }
+    }
+
+    match instance {
+        Value::Number(n) => check_number(schema, n.as_f64().unwrap_or_default(), path, errors),
+        Value::String(s) => check_string(schema, s, path, errors),
+        Value::Array(items) => check_array(root, schema, items, path, errors),
+        Value::Object(map) => check_object(root, schema, map, path, errors),
+        _ => {}
+    }
+}
+
+fn check_number(schema: &Map<String, Value>, n: f64, path: &str, errors: &mut Vec<String>) {
+    let limit = |key: &str| schema.get(key).and_then(Value::as_f64);
+    // Draft 4 makes `minimum` and `maximum` exclusive with a boolean.
+    let exclusive = |key: &str| schema.get(key) == Some(&Value::Bool(true));
+    if let Some(min) = limit("minimum") {
+        if exclusive("exclusiveMinimum") {
+            if n <= min {
+                errors.push(format!("{}: {} must be greater than {}", path, n, min));
+            }
+        } else if n < min {
+            errors.push(format!("{}: {} is less than the minimum {}", path, n, min));
+        }
+    }
+    if let Some(max) = limit("maximum") {
+        if exclusive("exclusiveMaximum") {
+            if n >= max {
+                errors.push(format!("{}: {} must be less than {}", path, n, max));
+            }
+        } else if n > max {
+            errors.push(format!(
+                "{}: {} is greater than the maximum {}",
+                path, n, max
+            ));
+        }
+    }
+    if let Some(min) = limit("exclusiveMinimum") {
+        if n <= min {
+            errors.push(format!("{}: {} must be greater than {}", path, n, min));
+        }
+    }
+    if let Some(max) = limit("exclusiveMaximum") {
+        if n >= max {
+            errors.push(format!("{}: {} must be less than {}", path, n, max));
+        }
+    }
+    if let Some(step) = limit("multipleOf") {
+        // Allow for rounding, so that 0.3 is a multiple of 0.1.
+        let quotient = n / step;
+        if step > 0.0 && (quotient - quotient.round()).abs() > 1e-9 * quotient.abs().max(1.0) {
+            errors.push(format!("{}: {} is not a multiple of {}", path, n, step));
+        }
+    }
+}
+
+fn check_string(schema: &Map<String, Value>, s: &str, path: &str, errors: &mut Vec<String>) {
+    let length = s.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if length < min {
+            errors.push(format!("{}: shorter than {} characters", path, min));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if length > max {
+            errors.push(format!("{}: longer than {} characters", path, max));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        match regex::Regex::new(pattern) {
+            Ok(re) if !re.is_match(s) => {
+                errors.push(format!("{}: doesn't match the pattern {}", path, pattern))
+            }
+            Ok(_) => {}
+            Err(_) => errors.push(format!("{}: invalid pattern {} in schema", path, pattern)),
+        }
+    }
+}
+
+fn check_array(
+    root: &Value,
+    schema: &Map<String, Value>,
+    items: &[Value],
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let count = items.len() as u64;
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if count < min {
+            errors.push(format!("{}: fewer than {} items", path, min));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if count > max {
+            errors.push(format!("{}: more than {} items", path, max));
+        }
+    }
+    if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+        for (i, item) in items.iter().enumerate() {
+            if items[..i].contains(item) {
+                errors.push(format!("{}[{}]: duplicate item", path, i));
+            }
+        }
+    }
+
+    // `prefixItems` (2020-12) or an `items` array (older drafts) describe a
+    // tuple; `items` as a schema then applies to the remaining elements.
+    let tuple = match (schema.get("prefixItems"), schema.get("items")) {
+        (Some(Value::Array(prefix)), _) | (None, Some(Value::Array(prefix))) => prefix.as_slice(),
+        _ => &[],
+    };
+    let rest = match schema.get("items") {
+        Some(Value::Array(_)) => schema.get("additionalItems"),
+        other => other,
+    };
+    for (i, item) in items.iter().enumerate() {
+        let item_path = format!("{}[{}]", path, i);
+        match tuple.get(i) {
+            Some(item_schema) => check(root, item_schema, item, &item_path, 0, errors),
+            None => {
+                if let Some(item_schema) = rest {
+                    check(root, item_schema, item, &item_path, 0, errors);
+                }
+            }
+        }
+    }
+}
+
+fn check_object(
+    root: &Value,
+    schema: &Map<String, Value>,
+    map: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !map.contains_key(name) {
+                errors.push(format!("{}: missing required property \"{}\"", path, name));
+            }
+        }
+    }
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (name, value) in map {
+        let property_path = format!("{}.{}", path, name);
+        match properties.and_then(|p| p.get(name)) {
+            Some(property) => check(root, property, value, &property_path, 0, errors),
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => {
+                    errors.push(format!("{}: property not allowed", property_path))
+                }
+                Some(additional) => check(root, additional, value, &property_path, 0, errors),
+                None => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_type_and_required_violations() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {"name": {"type": "string"}}
+        });
+        assert_eq!(
+            validate(&schema, &json!({"name": 1})),
+            vec![
+                "$: missing required property \"id\"",
+                "$.name: expected string, got integer"
+            ]
+        );
+        assert!(validate(&schema, &json!({"id": 1, "name": "a"})).is_empty());
+    }
+
+    #[test]
+    fn ref_cycles_are_violations() {
+        assert_eq!(
+            validate(&json!({"$ref": "#"}), &json!(1)),
+            vec!["$: $ref # loops back on itself"]
+        );
+        let schema = json!({
+            "$ref": "#/$defs/a",
+            "$defs": {"a": {"$ref": "#/$defs/b"}, "b": {"$ref": "#/$defs/a"}}
+        });
+        assert_eq!(validate(&schema, &json!(1)).len(), 1);
+    }
+
+    #[test]
+    fn recursive_refs_follow_the_instance() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"child": {"$ref": "#"}, "value": {"type": "integer"}}
+        });
+        let instance = json!({"child": {"child": {"value": "x"}}});
+        assert_eq!(
+            validate(&schema, &instance),
+            vec!["$.child.child.value: expected integer, got string"]
+        );
+    }
+
+    #[test]
+    fn multiple_of_allows_rounding() {
+        let schema = json!({"multipleOf": 0.1});
+        assert!(validate(&schema, &json!(0.3)).is_empty());
+        assert_eq!(validate(&schema, &json!(0.35)).len(), 1);
+        assert!(validate(&json!({"multipleOf": 3}), &json!(9)).is_empty());
+    }
+
+    #[test]
+    fn exclusive_limits_of_both_drafts() {
+        let draft4 = json!({"minimum": 1, "exclusiveMinimum": true, "maximum": 3});
+        assert_eq!(validate(&draft4, &json!(1)).len(), 1);
+        assert!(validate(&draft4, &json!(3)).is_empty());
+        let later = json!({"exclusiveMaximum": 3});
+        assert_eq!(validate(&later, &json!(3)).len(), 1);
+        assert!(validate(&later, &json!(2)).is_empty());
+    }
+}
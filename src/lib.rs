@@ -1,4 +1,19 @@
 mod app;
 pub use app::HttpApp;
 
+mod base64;
+mod csv;
+mod diff;
+mod hex;
+mod http_status;
+mod json_format;
+mod json_keys;
+mod json_path;
+mod json_schema;
+mod logger;
+mod sample;
 mod syntax_highlighting;
+mod text_search;
+mod variables;
+mod xml;
+mod yaml;
@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Lines kept for the in-app log panel; older ones are dropped.
+const MAX_LINES: usize = 2000;
+
+/// Collects `log` records from ureq and orient itself for the log panel,
+/// optionally appending them to a file as well.
+struct Logger {
+    lines: Mutex<VecDeque<String>>,
+    file: Mutex<Option<File>>,
+}
+
+static LOGGER: Logger = Logger {
+    lines: Mutex::new(VecDeque::new()),
+    file: Mutex::new(None),
+};
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            % (24 * 60 * 60);
+        let line = format!(
+            "{:02}:{:02}:{:02} {:<5} {}: {}",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = writeln!(file, "{}", line);
+        }
+        let mut lines = self.lines.lock().unwrap();
+        if lines.len() >= MAX_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    fn flush(&self) {
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Install the logger; calling it again only changes the level.
+pub fn init(level: log::LevelFilter) {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+}
+
+/// Also append log lines to `path`, or stop writing to a file if it's empty.
+pub fn set_file(path: &str) -> std::io::Result<()> {
+    let file = if path.trim().is_empty() {
+        None
+    } else {
+        Some(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path.trim())?,
+        )
+    };
+    *LOGGER.file.lock().unwrap() = file;
+    Ok(())
+}
+
+pub fn lines() -> Vec<String> {
+    LOGGER.lines.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear() {
+    LOGGER.lines.lock().unwrap().clear();
+}
@@ -0,0 +1,145 @@
+use serde_json::{Map, Value};
+
+/// Recursive `$ref`s are cut off at this depth.
+const MAX_DEPTH: usize = 16;
+
+/// A placeholder instance of `schema`, as a starting point for a request
+/// body. Uses `default`, `examples` and `const` where given, otherwise the
+/// first `enum` value or an empty value of the schema's type.
+pub fn from_schema(schema: &Value) -> Value {
+    sample(schema, schema, 0)
+}
+
+/// Like `from_schema`, resolving `$ref`s against `root`, the document
+/// `schema` is part of.
+pub fn from_schema_in(root: &Value, schema: &Value) -> Value {
+    sample(root, schema, 0)
+}
+
+fn sample(root: &Value, schema: &Value, depth: usize) -> Value {
+    let Value::Object(schema) = schema else {
+        return Value::Null;
+    };
+    if depth > MAX_DEPTH {
+        return Value::Null;
+    }
+    if let Some(target) = schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| root.pointer(r.strip_prefix('#')?))
+    {
+        return sample(root, target, depth + 1);
+    }
+    for key in ["default", "const", "example"] {
+        if let Some(value) = schema.get(key) {
+            return value.clone();
+        }
+    }
+    for key in ["examples", "enum"] {
+        if let Some(value) = schema
+            .get(key)
+            .and_then(Value::as_array)
+            .and_then(|v| v.first())
+        {
+            return value.clone();
+        }
+    }
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        let mut merged = Map::new();
+        for sub in all {
+            match sample(root, sub, depth + 1) {
+                Value::Object(map) => merged.extend(map),
+                other if all.len() == 1 => return other,
+                _ => {}
+            }
+        }
+        return Value::Object(merged);
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema
+            .get(key)
+            .and_then(Value::as_array)
+            .and_then(|v| v.first())
+        {
+            return sample(root, first, depth + 1);
+        }
+    }
+
+    let types = match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    // Prefer a concrete type over `null` in `["string", "null"]`.
+    let kind = types
+        .iter()
+        .find(|t| **t != "null")
+        .or(types.first())
+        .copied()
+        .unwrap_or(if schema.contains_key("properties") {
+            "object"
+        } else if schema.contains_key("items") {
+            "array"
+        } else {
+            ""
+        });
+    match kind {
+        "object" => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            Value::Object(
+                properties
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, property)| (name.clone(), sample(root, property, depth + 1)))
+                    .collect(),
+            )
+        }
+        "array" => {
+            let items = match (schema.get("prefixItems"), schema.get("items")) {
+                (Some(Value::Array(prefix)), _) | (None, Some(Value::Array(prefix))) => prefix
+                    .iter()
+                    .map(|item| sample(root, item, depth + 1))
+                    .collect(),
+                (_, Some(item)) => vec![sample(root, item, depth + 1)],
+                _ => Vec::new(),
+            };
+            Value::Array(items)
+        }
+        "string" => Value::String(
+            match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => "1970-01-01T00:00:00Z",
+                Some("date") => "1970-01-01",
+                Some("time") => "00:00:00",
+                Some("email") => "user@example.com",
+                Some("uri" | "url") => "https://example.com",
+                Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                _ => "",
+            }
+            .to_owned(),
+        ),
+        "integer" | "number" => schema
+            .get("minimum")
+            .or(schema.get("exclusiveMinimum"))
+            .cloned()
+            .unwrap_or(Value::from(0)),
+        "boolean" => Value::Bool(false),
+        _ => Value::Null,
+    }
+}
+
+/// `example` with every value replaced by an empty one of the same type,
+/// keeping the keys. Arrays are cut down to their first element.
+pub fn from_example(example: &Value) -> Value {
+    match example {
+        Value::Null => Value::Null,
+        Value::Bool(_) => Value::Bool(false),
+        Value::Number(_) => Value::from(0),
+        Value::String(_) => Value::String(String::new()),
+        Value::Array(items) => Value::Array(items.first().map(from_example).into_iter().collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), from_example(value)))
+                .collect(),
+        ),
+    }
+}
@@ -1,18 +1,20 @@
 use egui::text::LayoutJob;
 
-/// Memoized Code highlighting
-pub fn highlight(ctx: &egui::Context, code: &str) -> LayoutJob {
-    impl egui::util::cache::ComputerMut<&str, LayoutJob> for Highlighter {
-        fn compute(&mut self, code: &str) -> LayoutJob {
-            self.highlight(code)
+/// Memoized Code highlighting, using a syntax theme matching the current visuals.
+/// `language` is a file extension such as `"js"` or `"yaml"`.
+pub fn highlight(ctx: &egui::Context, code: &str, language: &str) -> LayoutJob {
+    impl egui::util::cache::ComputerMut<(bool, &str, &str), LayoutJob> for Highlighter {
+        fn compute(&mut self, (dark_mode, code, language): (bool, &str, &str)) -> LayoutJob {
+            self.highlight(dark_mode, code, language)
         }
     }
 
     type HighlightCache = egui::util::cache::FrameCache<LayoutJob, Highlighter>;
 
+    let dark_mode = ctx.style().visuals.dark_mode;
     let mut memory = ctx.memory();
     let highlight_cache = memory.caches.cache::<HighlightCache>();
-    highlight_cache.get(code)
+    highlight_cache.get((dark_mode, code, language))
 }
 struct Highlighter {
     ps: syntect::parsing::SyntaxSet,
@@ -30,29 +32,39 @@ impl Default for Highlighter {
 
 impl Highlighter {
     #[allow(clippy::unused_self, clippy::unnecessary_wraps)]
-    fn highlight(&self, code: &str) -> LayoutJob {
-        self.highlight_impl(code).unwrap_or_else(|| {
-            // Fallback:
-            LayoutJob::simple(
-                code.into(),
-                egui::FontId::monospace(12.0),
-                egui::Color32::LIGHT_GRAY,
-                f32::INFINITY,
-            )
-        })
+    fn highlight(&self, dark_mode: bool, code: &str, language: &str) -> LayoutJob {
+        self.highlight_impl(dark_mode, code, language)
+            .unwrap_or_else(|| {
+                // Fallback:
+                LayoutJob::simple(
+                    code.into(),
+                    egui::FontId::monospace(12.0),
+                    if dark_mode {
+                        egui::Color32::LIGHT_GRAY
+                    } else {
+                        egui::Color32::DARK_GRAY
+                    },
+                    f32::INFINITY,
+                )
+            })
     }
 
-    fn highlight_impl(&self, text: &str) -> Option<LayoutJob> {
+    fn highlight_impl(&self, dark_mode: bool, text: &str, language: &str) -> Option<LayoutJob> {
         use syntect::easy::HighlightLines;
         use syntect::highlighting::FontStyle;
         use syntect::util::LinesWithEndings;
 
         let syntax = self
             .ps
-            .find_syntax_by_name("js")
-            .or_else(|| self.ps.find_syntax_by_extension("js"))?;
+            .find_syntax_by_name(language)
+            .or_else(|| self.ps.find_syntax_by_extension(language))?;
 
-        let mut h = HighlightLines::new(syntax, &self.ts.themes["base16-ocean.light"]);
+        let theme = if dark_mode {
+            "base16-ocean.dark"
+        } else {
+            "base16-ocean.light"
+        };
+        let mut h = HighlightLines::new(syntax, &self.ts.themes[theme]);
 
         use egui::text::{LayoutSection, TextFormat};
 
@@ -98,3 +110,36 @@ fn as_byte_range(whole: &str, range: &str) -> std::ops::Range<usize> {
     let offset = range_start - whole_start;
     offset..(offset + range.len())
 }
+
+/// Give the text in `ranges` (sorted byte ranges of `job.text`) a background.
+pub fn mark(job: &mut LayoutJob, ranges: &[std::ops::Range<usize>], background: egui::Color32) {
+    if ranges.is_empty() {
+        return;
+    }
+    let mut sections = Vec::with_capacity(job.sections.len() + 2 * ranges.len());
+    for section in job.sections.drain(..) {
+        let mut start = section.byte_range.start;
+        let end = section.byte_range.end;
+        // Section boundaries: where marks start or stop inside this section.
+        let mut cuts: Vec<usize> = ranges
+            .iter()
+            .flat_map(|r| [r.start, r.end])
+            .filter(|&cut| cut > start && cut < end)
+            .collect();
+        cuts.push(end);
+        for cut in cuts {
+            let mut piece = section.clone();
+            piece.byte_range = start..cut;
+            if ranges.iter().any(|r| r.start <= start && cut <= r.end) {
+                piece.format.background = background;
+            }
+            // Only the first piece keeps the section's leading space.
+            if start != section.byte_range.start {
+                piece.leading_space = 0.0;
+            }
+            sections.push(piece);
+            start = cut;
+        }
+    }
+    job.sections = sections;
+}
@@ -0,0 +1,67 @@
+use std::ops::Range;
+
+/// Byte ranges of the non-overlapping, case-insensitive matches of `needle`
+/// in `text`, in order.
+pub fn find(text: &str, needle: &str) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    if needle.is_empty() {
+        return matches;
+    }
+    let mut from = 0;
+    while from < text.len() {
+        let Some((start, end)) = text[from..].char_indices().find_map(|(i, _)| {
+            match_at(&text[from + i..], needle).map(|len| (from + i, from + i + len))
+        }) else {
+            break;
+        };
+        matches.push(start..end);
+        from = end;
+    }
+    matches
+}
+
+/// Length in bytes of `needle` at the start of `text`, ignoring case.
+fn match_at(text: &str, needle: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for expected in needle.chars() {
+        let (_, c) = chars.next()?;
+        if !c.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(i, _)| i))
+}
+
+/// The lines of `text` with a match of `needle`.
+pub fn matching_lines(text: &str, needle: &str) -> String {
+    text.lines()
+        .filter(|line| !find(line, needle).is_empty())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_case_insensitive_matches() {
+        let body = "{\"Name\": \"Ann\", \"nickname\": \"ANNA\"}";
+        assert_eq!(find(body, "name").len(), 2);
+        assert_eq!(find(body, "ann"), vec![10..13, 29..32]);
+        assert!(find(body, "").is_empty());
+        assert!(find(body, "bob").is_empty());
+        // Matches don't overlap, and multi-byte characters are kept whole.
+        assert_eq!(find("aaaa", "aa"), vec![0..2, 2..4]);
+        assert_eq!(find("Ünïcode ünï", "ÜNÏ"), vec![0..5, 10..15]);
+    }
+
+    #[test]
+    fn keeps_matching_lines() {
+        assert_eq!(
+            matching_lines("id: 1\nname: a\nid: 2", "ID"),
+            "id: 1\nid: 2"
+        );
+        assert_eq!(matching_lines("a\nb", "c"), "");
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use uuid::Uuid;
+
+/// Replace every `{{name}}` token for which `lookup` returns a value.
+///
+/// Tokens that can't be resolved are left in place so they stay visible.
+pub fn resolve(text: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let token = &rest[start..start + end + 2];
+        out.push_str(&rest[..start]);
+        match lookup(token[2..token.len() - 2].trim()) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(token),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Names of all `{{name}}` tokens in `text`, in order of appearance.
+pub fn tokens(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        names.push(rest[start + 2..start + end].trim().to_owned());
+        rest = &rest[start + end + 2..];
+    }
+    names
+}
+
+/// Values for the built-in `{{$name}}` variables, generated fresh on every call.
+pub fn dynamic(name: &str) -> Option<String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    match name {
+        "$uuid" | "$guid" => Some(Uuid::new_v4().to_string()),
+        "$timestamp" => Some(now.as_secs().to_string()),
+        "$timestampMs" => Some(now.as_millis().to_string()),
+        "$randomInt" => Some((Uuid::new_v4().as_u128() % 1000).to_string()),
+        _ => None,
+    }
+}
+
+/// Where a variable's value comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    Request,
+    Folder,
+    Environment,
+    Global,
+    Dynamic,
+}
+
+impl Scope {
+    /// Scopes from highest to lowest precedence: the first one defining a name wins.
+    pub const RESOLUTION_ORDER: [Scope; 5] = [
+        Scope::Request,
+        Scope::Folder,
+        Scope::Environment,
+        Scope::Global,
+        Scope::Dynamic,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Scope::Request => "request",
+            Scope::Folder => "folder",
+            Scope::Environment => "environment",
+            Scope::Global => "global",
+            Scope::Dynamic => "dynamic",
+        }
+    }
+}
+
+/// `request > folder > environment > global > dynamic`, for showing next to variable editors.
+pub fn resolution_order() -> String {
+    Scope::RESOLUTION_ORDER
+        .iter()
+        .map(Scope::label)
+        .collect::<Vec<&str>>()
+        .join(" > ")
+}
+
+/// Merge variable sets, each tagged with its scope, into one map recording
+/// which scope every value came from. Sets listed first take precedence;
+/// variables with an empty name are ignored.
+pub fn merge<'a>(
+    sets: impl IntoIterator<Item = (Scope, &'a [(String, String)])>,
+) -> BTreeMap<String, (String, Scope)> {
+    let mut merged = BTreeMap::new();
+    for (scope, variables) in sets {
+        for (name, value) in variables {
+            if !name.is_empty() && !merged.contains_key(name) {
+                merged.insert(name.clone(), (value.clone(), scope));
+            }
+        }
+    }
+    merged
+}
+
+/// One line per token in `text` saying what it resolves to and from which
+/// scope, or `None` if there are no tokens.
+pub fn describe(text: &str, variables: &BTreeMap<String, (String, Scope)>) -> Option<String> {
+    let names = tokens(text);
+    if names.is_empty() {
+        return None;
+    }
+    let lines = names
+        .iter()
+        .map(|name| match variables.get(name) {
+            Some((value, scope)) => format!("{} = {} ({})", name, value, scope.label()),
+            None if dynamic(name).is_some() => {
+                format!("{}: generated on send ({})", name, Scope::Dynamic.label())
+            }
+            None => format!("{}: unresolved", name),
+        })
+        .collect::<Vec<String>>();
+    Some(lines.join("\n"))
+}
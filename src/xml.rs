@@ -0,0 +1,162 @@
+/// Whether responses of `content_type` hold XML (HTML excluded).
+pub fn is_xml(content_type: &str) -> bool {
+    let essence = essence(content_type);
+    matches!(essence.as_str(), "application/xml" | "text/xml")
+        || (essence.ends_with("+xml") && essence != "application/xhtml+xml")
+}
+
+/// Whether responses of `content_type` hold HTML.
+pub fn is_html(content_type: &str) -> bool {
+    matches!(
+        essence(content_type).as_str(),
+        "text/html" | "application/xhtml+xml"
+    )
+}
+
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    /// Self-closing elements, comments, declarations and CDATA sections.
+    Single(&'a str),
+    Text(&'a str),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+        let delimited = [("<!--", "-->"), ("<![CDATA[", "]]>"), ("<?", "?>")]
+            .into_iter()
+            .find(|(start, _)| rest.starts_with(start));
+        let end = match delimited {
+            Some((_, end)) => rest.find(end).map(|i| i + end.len()),
+            None => tag_end(rest),
+        }
+        .ok_or_else(|| {
+            format!(
+                "unterminated `{}`",
+                rest.chars().take(20).collect::<String>()
+            )
+        })?;
+        let tag = &rest[..end];
+        tokens.push(
+            if delimited.is_some() || tag.starts_with("<!") || tag.ends_with("/>") {
+                Token::Single(tag)
+            } else if tag.starts_with("</") {
+                Token::Close(tag)
+            } else {
+                Token::Open(tag)
+            },
+        );
+        rest = &rest[end..];
+    }
+    Ok(tokens)
+}
+
+/// End of the tag at the start of `text`, past its `>`; quoted attribute
+/// values may contain `>`.
+fn tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Re-indent an XML document, one element per line. Elements holding only
+/// text stay on one line; whitespace between elements is dropped.
+pub fn pretty(text: &str) -> Result<String, String> {
+    let tokens = tokenize(text.trim())?;
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        let line = match (&tokens[i], tokens.get(i + 1), tokens.get(i + 2)) {
+            (Token::Text(text), _, _) if text.trim().is_empty() => {
+                i += 1;
+                continue;
+            }
+            (Token::Open(open), Some(Token::Text(text)), Some(Token::Close(close))) => {
+                i += 3;
+                format!("{}{}{}", open, text.trim(), close)
+            }
+            (Token::Open(open), Some(Token::Close(close)), _) => {
+                i += 2;
+                format!("{}{}", open, close)
+            }
+            (Token::Open(open), _, _) => {
+                out.push_str(&"  ".repeat(depth));
+                out.push_str(open);
+                out.push('\n');
+                depth += 1;
+                i += 1;
+                continue;
+            }
+            (Token::Close(close), _, _) => {
+                depth = depth.saturating_sub(1);
+                i += 1;
+                close.to_string()
+            }
+            (Token::Single(tag), _, _) => {
+                i += 1;
+                tag.to_string()
+            }
+            (Token::Text(text), _, _) => {
+                i += 1;
+                text.trim().to_owned()
+            }
+        };
+        out.push_str(&"  ".repeat(depth));
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out.trim_end().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_xml_and_html() {
+        assert!(is_xml("application/xml; charset=utf-8"));
+        assert!(is_xml("Application/ATOM+XML"));
+        assert!(!is_xml("application/xhtml+xml"));
+        assert!(is_html("application/xhtml+xml"));
+        assert!(is_html("text/html;charset=UTF-8"));
+        assert!(!is_html("application/json"));
+    }
+
+    #[test]
+    fn reindents_elements() {
+        assert_eq!(
+            pretty(
+                "<?xml version=\"1.0\"?><list a=\"1>2\">\n <item>one</item><empty></empty><!-- c --><br/></list>"
+            ),
+            Ok("<?xml version=\"1.0\"?>\n<list a=\"1>2\">\n  <item>one</item>\n  <empty></empty>\n  <!-- c -->\n  <br/>\n</list>".to_owned())
+        );
+        assert!(pretty("<a><b").is_err());
+        assert!(pretty("<a><!-- open").is_err());
+    }
+}
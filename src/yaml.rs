@@ -0,0 +1,74 @@
+use serde_json::Value;
+use yaml_rust::{Yaml, YamlEmitter, YamlLoader};
+
+/// Whether responses of `content_type` hold YAML.
+pub fn is_yaml(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    matches!(
+        essence.as_str(),
+        "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml"
+    ) || essence.ends_with("+yaml")
+}
+
+fn load(text: &str) -> Result<Vec<Yaml>, String> {
+    YamlLoader::load_from_str(text).map_err(|err| err.to_string())
+}
+
+/// Re-emit a YAML document stream with consistent indentation.
+pub fn pretty(text: &str) -> Result<String, String> {
+    let mut out = String::new();
+    for doc in load(text)? {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        YamlEmitter::new(&mut out)
+            .dump(&doc)
+            .map_err(|err| format!("{:?}", err))?;
+    }
+    // The emitter starts every document with `---`; keep it only between documents.
+    Ok(out.strip_prefix("---\n").unwrap_or(&out).to_owned())
+}
+
+/// The documents as JSON: a single document as is, several as an array.
+pub fn to_json(text: &str) -> Result<Value, String> {
+    let mut docs: Vec<Value> = load(text)?.iter().map(yaml_to_json).collect();
+    Ok(match docs.len() {
+        1 => docs.remove(0),
+        _ => Value::Array(docs),
+    })
+}
+
+fn key_string(key: &Yaml) -> String {
+    match key {
+        Yaml::String(s) | Yaml::Real(s) => s.clone(),
+        Yaml::Integer(i) => i.to_string(),
+        Yaml::Boolean(b) => b.to_string(),
+        Yaml::Null => "null".to_owned(),
+        other => format!("{:?}", other),
+    }
+}
+
+fn yaml_to_json(yaml: &Yaml) -> Value {
+    match yaml {
+        Yaml::Real(s) => s
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map_or_else(|| Value::String(s.clone()), Value::Number),
+        Yaml::Integer(i) => Value::from(*i),
+        Yaml::String(s) => Value::String(s.clone()),
+        Yaml::Boolean(b) => Value::Bool(*b),
+        Yaml::Array(items) => Value::Array(items.iter().map(yaml_to_json).collect()),
+        Yaml::Hash(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (key_string(k), yaml_to_json(v)))
+                .collect(),
+        ),
+        Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => Value::Null,
+    }
+}